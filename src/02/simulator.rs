@@ -0,0 +1,122 @@
+use super::{parse_games, Bag, COLORS};
+use crate::rng::Rng;
+
+pub struct DrawRules {
+    pub min_sets: usize,
+    pub max_sets: usize,
+    pub min_cubes: usize,
+    pub max_cubes: usize,
+}
+
+impl Default for DrawRules {
+    fn default() -> Self {
+        Self {
+            min_sets: 1,
+            max_sets: 6,
+            min_cubes: 1,
+            max_cubes: 20,
+        }
+    }
+}
+
+pub struct Simulator {
+    bag: Bag,
+    rules: DrawRules,
+    rng: Rng,
+}
+
+impl Simulator {
+    pub fn new(bag: Bag, rules: DrawRules, seed: u64) -> Self {
+        if let Some(color) = COLORS.iter().find(|color| bag.get(color) < 0) {
+            panic!("Bag has a negative number of {} cubes", color);
+        }
+
+        if COLORS.iter().all(|color| bag.get(color) == 0) {
+            panic!("Bag is empty");
+        }
+
+        Self {
+            bag,
+            rules,
+            rng: Rng::new(seed),
+        }
+    }
+
+    fn draw_set(&mut self) -> Bag {
+        let mut cubes = COLORS
+            .iter()
+            .flat_map(|color| std::iter::repeat_n(*color, self.bag.get(color) as usize))
+            .collect::<Vec<&str>>();
+
+        let count = self
+            .rng
+            .range(self.rules.min_cubes, self.rules.max_cubes)
+            .clamp(1, cubes.len());
+
+        let mut set = Bag::default();
+        for _ in 0..count {
+            let index = self.rng.range(0, cubes.len() - 1);
+            let color = cubes.swap_remove(index);
+
+            set.set(color, set.get(color) + 1);
+        }
+
+        set
+    }
+
+    pub fn game(&mut self, id: i32) -> String {
+        let set_count = self
+            .rng
+            .range(self.rules.min_sets, self.rules.max_sets)
+            .max(1);
+
+        let sets = (0..set_count)
+            .map(|_| {
+                let set = self.draw_set();
+
+                let mut colors = COLORS
+                    .iter()
+                    .filter(|color| set.get(color) > 0)
+                    .collect::<Vec<_>>();
+
+                for i in (1..colors.len()).rev() {
+                    let j = self.rng.range(0, i);
+                    colors.swap(i, j);
+                }
+
+                colors
+                    .iter()
+                    .map(|color| format!("{} {}", set.get(color), color))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            })
+            .collect::<Vec<String>>();
+
+        format!("Game {}: {}", id, sets.join("; "))
+    }
+
+    pub fn games(&mut self, count: usize) -> String {
+        (1..=count)
+            .map(|id| self.game(id as i32))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    pub fn possible_probability(&mut self, limit: &Bag, trials: usize) -> f64 {
+        if trials == 0 {
+            return 0.0;
+        }
+
+        let possible = (0..trials)
+            .filter(|id| {
+                let record = self.game(*id as i32 + 1);
+
+                parse_games(&record)
+                    .iter()
+                    .all(|game| game.is_possible(limit))
+            })
+            .count();
+
+        possible as f64 / trials as f64
+    }
+}
@@ -1,8 +1,15 @@
+#[path = "./simulator.rs"]
+mod simulator;
+
+use simulator::{DrawRules, Simulator};
+use std::env;
 use std::fs;
 
+pub const COLORS: [&str; 3] = ["red", "green", "blue"];
+
 pub struct Cube {
-    color: String,
-    count: i32,
+    pub color: String,
+    pub count: i32,
 }
 
 impl Cube {
@@ -11,17 +18,99 @@ impl Cube {
     }
 }
 
-pub fn main() {
-    let input = fs::read_to_string("src/02/input.txt").expect("File not found");
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Bag {
+    pub red: i32,
+    pub green: i32,
+    pub blue: i32,
+}
+
+impl Bag {
+    pub fn new(red: i32, green: i32, blue: i32) -> Self {
+        Self { red, green, blue }
+    }
+
+    pub fn get(&self, color: &str) -> i32 {
+        match color {
+            "red" => self.red,
+            "green" => self.green,
+            "blue" => self.blue,
+            _ => panic!("Unknown color"),
+        }
+    }
+
+    pub fn set(&mut self, color: &str, count: i32) {
+        match color {
+            "red" => self.red = count,
+            "green" => self.green = count,
+            "blue" => self.blue = count,
+            _ => panic!("Unknown color"),
+        }
+    }
+
+    pub fn contains(&self, other: &Bag) -> bool {
+        COLORS
+            .iter()
+            .all(|color| self.get(color) >= other.get(color))
+    }
+
+    pub fn power(&self) -> i32 {
+        self.red * self.green * self.blue
+    }
+}
 
-    let games: Vec<(i32, Vec<Vec<Cube>>)> = input
+pub struct Game {
+    pub id: i32,
+    pub sets: Vec<Vec<Cube>>,
+}
+
+impl Game {
+    pub fn new(id: i32, sets: Vec<Vec<Cube>>) -> Self {
+        Self { id, sets }
+    }
+
+    pub fn min_bag(&self) -> Bag {
+        let mut bag = Bag::default();
+
+        for cube in self.sets.iter().flatten() {
+            if cube.count > bag.get(&cube.color) {
+                bag.set(&cube.color, cube.count);
+            }
+        }
+
+        bag
+    }
+
+    pub fn is_possible(&self, bag: &Bag) -> bool {
+        bag.contains(&self.min_bag())
+    }
+
+    pub fn draws(&self, color: &str) -> impl Iterator<Item = i32> + '_ {
+        let color = color.to_string();
+
+        self.sets.iter().map(move |set| {
+            set.iter()
+                .filter(|cube| cube.color == color)
+                .map(|cube| cube.count)
+                .sum()
+        })
+    }
+}
+
+pub struct ColorStats {
+    pub max: i32,
+    pub mean: f64,
+}
+
+pub fn parse_games(input: &str) -> Vec<Game> {
+    input
         .lines()
         .map(|row| {
-            let (game_str, sets_str) = row.split_once(":").unwrap();
-            let game_num = game_str.split(" ").nth(1).unwrap().parse::<i32>().unwrap();
+            let (game_str, sets_str) = row.split_once(':').unwrap();
+            let game_num = game_str.split(' ').nth(1).unwrap().parse::<i32>().unwrap();
 
             let sets = sets_str
-                .split(";")
+                .split(';')
                 .map(|set| {
                     set.split(',')
                         .map(|cube| {
@@ -36,51 +125,165 @@ pub fn main() {
                 })
                 .collect();
 
-            (game_num, sets)
+            Game::new(game_num, sets)
         })
-        .collect();
+        .collect()
+}
+
+pub fn consistent_bag(games: &[Game]) -> Bag {
+    games.iter().fold(Bag::default(), |acc, game| {
+        let min = game.min_bag();
+
+        Bag::new(
+            acc.red.max(min.red),
+            acc.green.max(min.green),
+            acc.blue.max(min.blue),
+        )
+    })
+}
+
+pub fn impossible_after_reduction(games: &[Game], bag: &Bag, color: &str, limit: i32) -> Vec<i32> {
+    let mut reduced = *bag;
+    reduced.set(color, limit);
 
-    let a = games
+    games
         .iter()
-        .filter(|(_, sets)| {
-            !sets.iter().flatten().any(|cube| match cube.color.as_str() {
-                "red" => cube.count > 12,
-                "green" => cube.count > 13,
-                "blue" => cube.count > 14,
-                _ => panic!("Unknown color"),
-            })
-        })
-        .map(|(game, _)| game)
-        .sum::<i32>();
+        .filter(|game| game.is_possible(bag) && !game.is_possible(&reduced))
+        .map(|game| game.id)
+        .collect()
+}
 
-    let b = games
+pub fn color_stats(games: &[Game], color: &str) -> ColorStats {
+    let draws = games
         .iter()
-        .map(|(_, sets)| {
-            let max_red = sets
-                .iter()
-                .flatten()
-                .filter(|cube| cube.color == "red")
-                .max_by_key(|cube| cube.count)
-                .unwrap();
+        .flat_map(|game| game.draws(color))
+        .collect::<Vec<i32>>();
 
-            let max_green = sets
-                .iter()
-                .flatten()
-                .filter(|cube| cube.color == "green")
-                .max_by_key(|cube| cube.count)
-                .unwrap();
+    let max = draws.iter().copied().max().unwrap_or(0);
+    let mean = if draws.is_empty() {
+        0.0
+    } else {
+        draws.iter().sum::<i32>() as f64 / draws.len() as f64
+    };
+
+    ColorStats { max, mean }
+}
 
-            let max_blue = sets
+fn parse_bag(args: &[String]) -> Bag {
+    match args {
+        [red, green, blue, ..] => Bag::new(
+            red.parse().expect("Invalid red count"),
+            green.parse().expect("Invalid green count"),
+            blue.parse().expect("Invalid blue count"),
+        ),
+        _ => Bag::new(12, 13, 14),
+    }
+}
+
+fn parse_seed(args: &[String]) -> u64 {
+    args.get(3)
+        .map(|seed| seed.parse().expect("Invalid seed"))
+        .unwrap_or(2023)
+}
+
+pub fn main() {
+    let args = env::args().skip(1).collect::<Vec<String>>();
+
+    match args.first().map(|arg| arg.as_str()) {
+        Some("generate") => {
+            let count = args
+                .get(1)
+                .expect("Missing game count")
+                .parse::<usize>()
+                .expect("Invalid game count");
+            let bag = parse_bag(&args[2..]);
+            let seed = parse_seed(&args[2..]);
+
+            let mut simulator = Simulator::new(bag, DrawRules::default(), seed);
+            println!("{}", simulator.games(count));
+
+            return;
+        }
+        Some("estimate") => {
+            let trials = args
+                .get(1)
+                .expect("Missing trial count")
+                .parse::<usize>()
+                .expect("Invalid trial count");
+            let bag = parse_bag(&args[2..]);
+            let seed = parse_seed(&args[2..]);
+
+            let mut simulator = Simulator::new(bag, DrawRules::default(), seed);
+            let probability = simulator.possible_probability(&Bag::new(12, 13, 14), trials);
+            println!(
+                "Probability of a possible game from {} red, {} green, {} blue: {:.4}",
+                bag.red, bag.green, bag.blue, probability
+            );
+
+            return;
+        }
+        _ => {}
+    }
+
+    let input = fs::read_to_string("src/02/input.txt").expect("File not found");
+    let games = parse_games(&input);
+
+    match args.first().map(|arg| arg.as_str()) {
+        Some("min-bags") => {
+            for game in games.iter() {
+                let bag = game.min_bag();
+                println!(
+                    "Game {}: {} red, {} green, {} blue (power {})",
+                    game.id,
+                    bag.red,
+                    bag.green,
+                    bag.blue,
+                    bag.power()
+                );
+            }
+        }
+        Some("consistent") => {
+            let bag = consistent_bag(&games);
+            println!(
+                "Consistent bags: at least {} red, {} green, {} blue",
+                bag.red, bag.green, bag.blue
+            );
+        }
+        Some("reduce") => {
+            let color = args.get(1).expect("Missing color");
+            let limit = args
+                .get(2)
+                .expect("Missing limit")
+                .parse::<i32>()
+                .expect("Invalid limit");
+            let bag = parse_bag(&args[3..]);
+
+            let ids = impossible_after_reduction(&games, &bag, color, limit);
+            println!(
+                "Impossible after reducing {} to {}: {:?}",
+                color, limit, ids
+            );
+        }
+        Some("stats") => {
+            for color in COLORS {
+                let stats = color_stats(&games, color);
+                println!("{}: max {}, mean draw {:.2}", color, stats.max, stats.mean);
+            }
+        }
+        Some(command) => panic!("Unknown command {}", command),
+        None => {
+            let bag = Bag::new(12, 13, 14);
+
+            let a = games
                 .iter()
-                .flatten()
-                .filter(|cube| cube.color == "blue")
-                .max_by_key(|cube| cube.count)
-                .unwrap();
+                .filter(|game| game.is_possible(&bag))
+                .map(|game| game.id)
+                .sum::<i32>();
 
-            max_red.count * max_green.count * max_blue.count
-        })
-        .sum::<i32>();
+            let b = games.iter().map(|game| game.min_bag().power()).sum::<i32>();
 
-    println!("Answer a: {}", a);
-    println!("Answer b: {}", b);
+            println!("Answer a: {}", a);
+            println!("Answer b: {}", b);
+        }
+    }
 }
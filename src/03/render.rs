@@ -0,0 +1,181 @@
+use super::schematic::{Label, Schematic};
+
+const RESET: &str = "\x1b[0m";
+const PART: &str = "\x1b[32m";
+const NON_PART: &str = "\x1b[31m";
+const GEAR: &str = "\x1b[1;33m";
+const SYMBOL: &str = "\x1b[36m";
+
+fn is_ratio_gear(schematic: &Schematic, symbol: usize) -> bool {
+    schematic.symbols[symbol].is_gear() && schematic.adjacent_numbers(symbol).len() == 2
+}
+
+fn gear_summary(schematic: &Schematic, symbol: usize) -> String {
+    let (x, y) = schematic.symbols[symbol].position;
+    let numbers = schematic.adjacent_numbers(symbol);
+
+    format!(
+        "Gear ({}, {}): {} * {} = {}",
+        x,
+        y,
+        numbers[0].value,
+        numbers[1].value,
+        numbers[0].value * numbers[1].value
+    )
+}
+
+pub fn render_ansi(schematic: &Schematic) -> String {
+    let mut output = String::new();
+
+    for y in 0..schematic.row {
+        for x in 0..schematic.col {
+            let (value, label) = schematic.cell(x, y);
+
+            let color = match label {
+                Label::Number(id) if schematic.adjacent_symbols(id).is_empty() => NON_PART,
+                Label::Number(_) => PART,
+                Label::Symbol(id) if is_ratio_gear(schematic, id) => GEAR,
+                Label::Symbol(_) => SYMBOL,
+                Label::Empty => {
+                    output.push(value);
+                    continue;
+                }
+            };
+
+            output.push_str(color);
+            output.push(value);
+            output.push_str(RESET);
+        }
+
+        output.push('\n');
+    }
+
+    for symbol in 0..schematic.symbols.len() {
+        if is_ratio_gear(schematic, symbol) {
+            output.push_str(&gear_summary(schematic, symbol));
+            output.push('\n');
+        }
+    }
+
+    output
+}
+
+fn escape(value: char) -> String {
+    match value {
+        '&' => "&amp;".to_string(),
+        '<' => "&lt;".to_string(),
+        '>' => "&gt;".to_string(),
+        '"' => "&quot;".to_string(),
+        value => value.to_string(),
+    }
+}
+
+fn number_title(schematic: &Schematic, id: usize) -> String {
+    let number = &schematic.numbers[id];
+    let symbols = schematic.adjacent_symbols(id);
+
+    if symbols.is_empty() {
+        return format!(
+            "{} ({}, {}): no adjacent symbols",
+            number.value, number.start, number.row
+        );
+    }
+
+    let neighbours = symbols
+        .iter()
+        .map(|symbol| {
+            let (x, y) = symbol.position;
+            format!("{} ({}, {})", escape(symbol.value), x, y)
+        })
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    format!(
+        "{} ({}, {}): adjacent to {}",
+        number.value, number.start, number.row, neighbours
+    )
+}
+
+fn symbol_title(schematic: &Schematic, id: usize) -> String {
+    if is_ratio_gear(schematic, id) {
+        return gear_summary(schematic, id);
+    }
+
+    let symbol = &schematic.symbols[id];
+    let (x, y) = symbol.position;
+    let neighbours = schematic
+        .adjacent_numbers(id)
+        .iter()
+        .map(|number| number.value.to_string())
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    format!("{} ({}, {}): [{}]", escape(symbol.value), x, y, neighbours)
+}
+
+pub fn render_html(schematic: &Schematic) -> String {
+    let mut output = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<style>\n\
+         .part { color: green; }\n\
+         .non-part { color: red; }\n\
+         .gear { color: orange; font-weight: bold; }\n\
+         .symbol { color: teal; }\n\
+         span[title] { cursor: help; }\n\
+         </style>\n</head>\n<body>\n<pre>\n",
+    );
+
+    for y in 0..schematic.row {
+        let mut x = 0;
+
+        while x < schematic.col {
+            let (value, label) = schematic.cell(x, y);
+
+            match label {
+                Label::Number(id) => {
+                    let number = &schematic.numbers[id];
+                    let class = if schematic.adjacent_symbols(id).is_empty() {
+                        "non-part"
+                    } else {
+                        "part"
+                    };
+
+                    let digits = (number.start..number.end)
+                        .map(|x| schematic.cell(x, y).0)
+                        .collect::<String>();
+
+                    output.push_str(&format!(
+                        "<span class=\"{}\" title=\"{}\">{}</span>",
+                        class,
+                        number_title(schematic, id),
+                        digits
+                    ));
+
+                    x = number.end;
+                    continue;
+                }
+                Label::Symbol(id) => {
+                    let class = if is_ratio_gear(schematic, id) {
+                        "gear"
+                    } else {
+                        "symbol"
+                    };
+
+                    output.push_str(&format!(
+                        "<span class=\"{}\" title=\"{}\">{}</span>",
+                        class,
+                        symbol_title(schematic, id),
+                        escape(value)
+                    ));
+                }
+                Label::Empty => output.push_str(&escape(value)),
+            }
+
+            x += 1;
+        }
+
+        output.push('\n');
+    }
+
+    output.push_str("</pre>\n</body>\n</html>\n");
+    output
+}
@@ -0,0 +1,228 @@
+#[derive(Debug, PartialEq, Clone, Hash, Eq)]
+pub struct Number {
+    pub value: i32,
+    pub row: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, PartialEq, Clone, Hash, Eq)]
+pub struct Symbol {
+    pub value: char,
+    pub position: (usize, usize),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Hash, Eq)]
+pub enum Label {
+    Number(usize),
+    Symbol(usize),
+    Empty,
+}
+
+pub struct Schematic {
+    pub row: usize,
+    pub col: usize,
+    cells: Vec<char>,
+    labels: Vec<Label>,
+    pub numbers: Vec<Number>,
+    pub symbols: Vec<Symbol>,
+    symbol_numbers: Vec<Vec<usize>>,
+    number_symbols: Vec<Vec<usize>>,
+}
+
+impl Symbol {
+    pub fn is_gear(&self) -> bool {
+        self.value == '*'
+    }
+}
+
+impl Schematic {
+    pub fn new(input: &str) -> Self {
+        let lines = input.lines().collect::<Vec<&str>>();
+        let row = lines.len();
+        let col = lines.first().map_or(0, |line| line.len());
+
+        if let Some(y) = lines.iter().position(|line| line.len() != col) {
+            panic!(
+                "Row {} has {} columns, expected {}",
+                y + 1,
+                lines[y].len(),
+                col
+            );
+        }
+
+        let mut numbers: Vec<Number> = Vec::new();
+        let mut symbols = Vec::new();
+        let mut cells = vec!['.'; row * col];
+        let mut labels = vec![Label::Empty; row * col];
+
+        for (y, line) in lines.iter().enumerate() {
+            let mut current: Option<usize> = None;
+
+            for (x, value) in line.chars().enumerate() {
+                cells[y * col + x] = value;
+
+                match value {
+                    '.' => current = None,
+                    value if value.is_ascii_digit() => {
+                        let digit = value.to_digit(10).unwrap() as i32;
+
+                        let id = match current {
+                            Some(id) => {
+                                let number = &mut numbers[id];
+                                number.value = number.value * 10 + digit;
+                                number.end = x + 1;
+                                id
+                            }
+                            None => {
+                                numbers.push(Number {
+                                    value: digit,
+                                    row: y,
+                                    start: x,
+                                    end: x + 1,
+                                });
+                                numbers.len() - 1
+                            }
+                        };
+
+                        labels[y * col + x] = Label::Number(id);
+                        current = Some(id);
+                    }
+                    value => {
+                        labels[y * col + x] = Label::Symbol(symbols.len());
+                        symbols.push(Symbol {
+                            value,
+                            position: (x, y),
+                        });
+                        current = None;
+                    }
+                }
+            }
+        }
+
+        let mut symbol_numbers = vec![Vec::new(); symbols.len()];
+        let mut number_symbols = vec![Vec::new(); numbers.len()];
+
+        for (symbol_id, symbol) in symbols.iter().enumerate() {
+            let (x, y) = symbol.position;
+
+            for ny in y.saturating_sub(1)..=(y + 1).min(row - 1) {
+                for nx in x.saturating_sub(1)..=(x + 1).min(col - 1) {
+                    if let Label::Number(number_id) = labels[ny * col + nx] {
+                        if !symbol_numbers[symbol_id].contains(&number_id) {
+                            symbol_numbers[symbol_id].push(number_id);
+                            number_symbols[number_id].push(symbol_id);
+                        }
+                    }
+                }
+            }
+        }
+
+        Self {
+            row,
+            col,
+            cells,
+            labels,
+            numbers,
+            symbols,
+            symbol_numbers,
+            number_symbols,
+        }
+    }
+
+    pub fn cell(&self, x: usize, y: usize) -> (char, Label) {
+        let index = y * self.col + x;
+
+        (self.cells[index], self.labels[index])
+    }
+
+    pub fn adjacent_numbers(&self, symbol: usize) -> Vec<&Number> {
+        self.symbol_numbers[symbol]
+            .iter()
+            .map(|&id| &self.numbers[id])
+            .collect()
+    }
+
+    pub fn adjacent_symbols(&self, number: usize) -> Vec<&Symbol> {
+        self.number_symbols[number]
+            .iter()
+            .map(|&id| &self.symbols[id])
+            .collect()
+    }
+
+    pub fn part_numbers(&self) -> Vec<&Number> {
+        self.numbers
+            .iter()
+            .enumerate()
+            .filter(|(id, _)| !self.number_symbols[*id].is_empty())
+            .map(|(_, number)| number)
+            .collect()
+    }
+
+    pub fn sum_adjacent_to(&self, class: &str) -> i32 {
+        self.numbers
+            .iter()
+            .enumerate()
+            .filter(|(id, _)| {
+                self.adjacent_symbols(*id)
+                    .iter()
+                    .any(|symbol| class.contains(symbol.value))
+            })
+            .map(|(_, number)| number.value)
+            .sum()
+    }
+
+    pub fn gears(&self, count: usize) -> Vec<(&Symbol, Vec<&Number>)> {
+        self.symbols
+            .iter()
+            .enumerate()
+            .filter(|(_, symbol)| symbol.is_gear())
+            .map(|(id, symbol)| (symbol, self.adjacent_numbers(id)))
+            .filter(|(_, numbers)| numbers.len() == count)
+            .collect()
+    }
+
+    pub fn gear_ratios(&self) -> Vec<i32> {
+        self.gears(2)
+            .iter()
+            .map(|(_, numbers)| numbers.iter().map(|number| number.value).product())
+            .collect()
+    }
+
+    pub fn shared_numbers(&self) -> Vec<(&Number, Vec<&Symbol>)> {
+        self.numbers
+            .iter()
+            .enumerate()
+            .map(|(id, number)| (number, self.adjacent_symbols(id)))
+            .filter(|(_, symbols)| symbols.len() > 1)
+            .collect()
+    }
+
+    pub fn adjacency_graph(&self) -> String {
+        let mut lines = vec!["graph schematic {".to_string()];
+
+        for (id, symbol) in self.symbols.iter().enumerate() {
+            let (x, y) = symbol.position;
+            lines.push(format!(
+                "    s{} [label=\"{} ({},{})\", shape=box];",
+                id, symbol.value, x, y
+            ));
+        }
+
+        for (id, number) in self.numbers.iter().enumerate() {
+            lines.push(format!(
+                "    n{} [label=\"{} ({},{})\"];",
+                id, number.value, number.start, number.row
+            ));
+        }
+
+        for (symbol_id, numbers) in self.symbol_numbers.iter().enumerate() {
+            for number_id in numbers {
+                lines.push(format!("    s{} -- n{};", symbol_id, number_id));
+            }
+        }
+
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+}
@@ -1,10 +1,251 @@
-#[path = "./task_a.rs"]
-mod task_a;
+#[path = "./schematic.rs"]
+mod schematic;
 
-#[path = "./task_b.rs"]
-mod task_b;
+#[path = "./render.rs"]
+mod render;
+
+use crate::rng::Rng;
+use schematic::Schematic;
+use std::env;
+use std::fs;
+use std::time::Instant;
+
+const SYMBOLS: [char; 10] = ['*', '#', '+', '$', '/', '=', '%', '@', '&', '-'];
+
+fn generate(rows: usize, cols: usize, seed: u64) -> String {
+    let mut rng = Rng::new(seed);
+
+    let mut output = String::with_capacity(rows * (cols + 1));
+
+    for _ in 0..rows {
+        let mut x = 0;
+
+        while x < cols {
+            match rng.range(0, 9) {
+                0..=2 => {
+                    let length = rng.range(1, 3);
+
+                    for _ in 0..length.min(cols - x) {
+                        output.push(char::from(b'0' + rng.range(0, 9) as u8));
+                    }
+                    x += length.min(cols - x);
+
+                    if x < cols {
+                        output.push('.');
+                        x += 1;
+                    }
+                }
+                3 => {
+                    output.push(SYMBOLS[rng.range(0, SYMBOLS.len() - 1)]);
+                    x += 1;
+                }
+                _ => {
+                    output.push('.');
+                    x += 1;
+                }
+            }
+        }
+
+        output.push('\n');
+    }
+
+    output
+}
+
+fn reference_part_sum(input: &str) -> i64 {
+    let grid = input
+        .lines()
+        .map(|line| line.chars().collect::<Vec<char>>())
+        .collect::<Vec<Vec<char>>>();
+
+    let cells = grid
+        .iter()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(x, &value)| ((x, y), value))
+        })
+        .collect::<Vec<((usize, usize), char)>>();
+
+    let is_symbol = |x: isize, y: isize| {
+        y >= 0
+            && x >= 0
+            && grid
+                .get(y as usize)
+                .and_then(|row| row.get(x as usize))
+                .is_some_and(|&value| value != '.' && !value.is_ascii_digit())
+    };
+
+    let mut parts: Vec<(Vec<(usize, usize)>, i64)> = Vec::new();
+
+    for &((x, y), _) in cells.iter().filter(|(_, value)| value.is_ascii_digit()) {
+        let adjacent =
+            (-1..=1).any(|dy| (-1..=1).any(|dx| is_symbol(x as isize + dx, y as isize + dy)));
+
+        if !adjacent {
+            continue;
+        }
+
+        let row = &grid[y];
+        let start = (0..=x)
+            .rev()
+            .take_while(|&i| row[i].is_ascii_digit())
+            .last()
+            .unwrap();
+        let end = (x..row.len())
+            .take_while(|&i| row[i].is_ascii_digit())
+            .last()
+            .unwrap();
+        let points = (start..=end)
+            .map(|i| (i, y))
+            .collect::<Vec<(usize, usize)>>();
+
+        let value = cells
+            .iter()
+            .filter(|(position, _)| points.contains(position))
+            .map(|(_, value)| *value)
+            .collect::<String>()
+            .parse::<i64>()
+            .unwrap();
+
+        parts.push((points, value));
+    }
+
+    parts
+        .iter()
+        .enumerate()
+        .filter(|(index, (points, _))| {
+            !parts[index + 1..]
+                .iter()
+                .any(|(other, _)| points.iter().any(|point| other.contains(point)))
+        })
+        .map(|(_, (_, value))| value)
+        .sum()
+}
+
+fn bench() {
+    for size in [100, 200, 400, 800, 2000, 4000] {
+        let input = generate(size, size, size as u64);
+
+        let start = Instant::now();
+        let schematic = Schematic::new(&input);
+        let a: i64 = schematic
+            .part_numbers()
+            .iter()
+            .map(|number| number.value as i64)
+            .sum();
+        let b: i64 = schematic
+            .gear_ratios()
+            .iter()
+            .map(|ratio| *ratio as i64)
+            .sum();
+        let elapsed = start.elapsed();
+
+        println!(
+            "{}x{}: {} numbers, {} symbols, a {}, b {}",
+            size,
+            size,
+            schematic.numbers.len(),
+            schematic.symbols.len(),
+            a,
+            b
+        );
+        println!(
+            "  Schematic: {:?} ({:.1} ns/cell)",
+            elapsed,
+            elapsed.as_nanos() as f64 / (size * size) as f64
+        );
+
+        if size > 200 {
+            println!("  Reference: skipped, grows with cells x digits");
+            continue;
+        }
+
+        let start = Instant::now();
+        let reference = reference_part_sum(&input);
+        let reference_elapsed = start.elapsed();
+
+        assert_eq!(a, reference, "Schematic disagrees with reference scan");
+        println!(
+            "  Reference: {:?} ({:.1} ns/cell)",
+            reference_elapsed,
+            reference_elapsed.as_nanos() as f64 / (size * size) as f64
+        );
+    }
+}
 
 pub fn main() {
-    task_a::main();
-    task_b::main();
+    let args = env::args().skip(1).collect::<Vec<String>>();
+
+    if args.first().map(|arg| arg.as_str()) == Some("bench") {
+        bench();
+        return;
+    }
+
+    let input = fs::read_to_string("src/03/input.txt").expect("File not found");
+
+    let schematic = Schematic::new(&input);
+
+    match args.first().map(|arg| arg.as_str()) {
+        Some("sum") => {
+            let class = args.get(1).expect("Missing symbol class");
+
+            println!(
+                "Sum adjacent to {}: {}",
+                class,
+                schematic.sum_adjacent_to(class)
+            );
+        }
+        Some("gears") => {
+            let count = args
+                .get(1)
+                .map(|count| count.parse::<usize>().expect("Invalid count"))
+                .unwrap_or(2);
+
+            for (symbol, numbers) in schematic.gears(count) {
+                let (x, y) = symbol.position;
+                let values = numbers
+                    .iter()
+                    .map(|number| number.value)
+                    .collect::<Vec<i32>>();
+
+                println!("Gear at ({}, {}): {:?}", x, y, values);
+            }
+        }
+        Some("shared") => {
+            for (number, symbols) in schematic.shared_numbers() {
+                let values = symbols
+                    .iter()
+                    .map(|symbol| symbol.value)
+                    .collect::<String>();
+
+                println!(
+                    "{} at ({}, {}): {}",
+                    number.value, number.start, number.row, values
+                );
+            }
+        }
+        Some("graph") => println!("{}", schematic.adjacency_graph()),
+        Some("render") => match args.get(1) {
+            Some(path) => {
+                fs::write(path, render::render_html(&schematic)).expect("Unable to write file");
+                println!("Wrote {}", path);
+            }
+            None => print!("{}", render::render_ansi(&schematic)),
+        },
+        Some(command) => panic!("Unknown command {}", command),
+        None => {
+            let a: i32 = schematic
+                .part_numbers()
+                .iter()
+                .map(|number| number.value)
+                .sum();
+
+            let b: i32 = schematic.gear_ratios().iter().sum();
+
+            println!("Answer a: {}", a);
+            println!("Answer b: {}", b);
+        }
+    }
 }
@@ -1,17 +1,70 @@
+use crate::rng::Rng;
 use itertools::Itertools;
-use std::fs;
+use std::env;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::time::Instant;
 
-pub fn main() {
-    let input = fs::read_to_string("src/04/input.txt").expect("File not found");
+pub struct Card {
+    winning: u128,
+    own: u128,
+}
+
+impl Card {
+    pub fn parse(line: &str) -> Self {
+        let (_, numbers_str) = line.split_once(':').expect("Could not parse input");
+        let (winning_str, own_str) = numbers_str.split_once('|').expect("Could not parse input");
+
+        Self {
+            winning: Self::to_bitset(winning_str),
+            own: Self::to_bitset(own_str),
+        }
+    }
+
+    fn to_bitset(hand: &str) -> u128 {
+        hand.split_whitespace().fold(0, |bitset, number| {
+            let number = number.parse::<u32>().expect("Invalid number");
+            if number >= u128::BITS {
+                panic!("Number {} does not fit in card bitset", number);
+            }
+
+            bitset | 1 << number
+        })
+    }
+
+    pub fn matches(&self) -> usize {
+        (self.winning & self.own).count_ones() as usize
+    }
+}
+
+pub struct Propagation {
+    pub card_counts: Vec<usize>,
+    pub received: Vec<Vec<(usize, usize)>>,
+}
+
+fn read_cards(mut reader: impl BufRead) -> impl Iterator<Item = Card> {
+    let mut line = String::new();
+
+    std::iter::from_fn(move || loop {
+        line.clear();
 
-    let match_count = input
+        match reader.read_line(&mut line).expect("Could not read line") {
+            0 => return None,
+            _ if line.trim().is_empty() => continue,
+            _ => return Some(Card::parse(&line)),
+        }
+    })
+}
+
+fn parse_match_counts(input: &str) -> Vec<usize> {
+    input
         .lines()
         .map(|line| {
             let (winning_hand, own_hand) = line
-                .split_once(":")
+                .split_once(':')
                 .unwrap()
                 .1
-                .split("|")
+                .split('|')
                 .map(|hand| {
                     hand.split(' ')
                         .filter_map(|number| number.parse::<i32>().ok())
@@ -25,24 +78,170 @@ pub fn main() {
                 .filter(|item| winning_hand.contains(item))
                 .count()
         })
-        .collect::<Vec<_>>();
-
-    let a: usize = match_count
-        .iter()
-        .map(|&count| if count > 0 { 1 << (count - 1) } else { 0 })
-        .sum();
+        .collect()
+}
 
+fn propagate(match_count: &[usize]) -> Result<Propagation, String> {
     let mut card_counts = vec![0; match_count.len()];
-    for (card_index, cards) in match_count.iter().enumerate() {
-        for target_index in (card_index + 1)..(card_index + cards + 1) {
-            card_counts[target_index] += card_counts[card_index] + 1;
+    let mut received = vec![Vec::new(); match_count.len()];
+
+    for (card_index, &cards) in match_count.iter().enumerate() {
+        if card_index + cards >= match_count.len() {
+            return Err(format!(
+                "Card {} wins copies of cards {}-{}, but the last card is {}",
+                card_index + 1,
+                card_index + 2,
+                card_index + cards + 1,
+                match_count.len()
+            ));
         }
 
         card_counts[card_index] += 1;
+
+        for target_index in (card_index + 1)..(card_index + cards + 1) {
+            card_counts[target_index] += card_counts[card_index];
+            received[target_index].push((card_index, card_counts[card_index]));
+        }
     }
 
-    let b: usize = card_counts.iter().sum();
+    Ok(Propagation {
+        card_counts,
+        received,
+    })
+}
+
+fn generate(path: &str, count: usize) {
+    let mut writer = BufWriter::new(File::create(path).expect("Unable to create file"));
+    let mut rng = Rng::new(4);
+
+    let mut numbers = (1..100).collect::<Vec<u64>>();
 
-    println!("Answer a: {}", a);
-    println!("Answer b: {}", b);
+    for id in 1..=count {
+        for i in 0..35 {
+            let j = rng.range(i, numbers.len() - 1);
+            numbers.swap(i, j);
+        }
+
+        let winning = numbers[..10].iter().map(|n| format!("{:>2}", n)).join(" ");
+        let offset = rng.range(0, 10);
+        let own = numbers[offset..offset + 25]
+            .iter()
+            .map(|n| format!("{:>2}", n))
+            .join(" ");
+
+        writeln!(writer, "Card {}: {} | {}", id, winning, own).expect("Unable to write file");
+    }
+}
+
+fn bench(count: usize) {
+    let path = env::temp_dir().join("aoc2023-04-bench.txt");
+    let path = path.to_str().unwrap();
+
+    generate(path, count);
+
+    let start = Instant::now();
+    let input = fs::read_to_string(path).expect("File not found");
+    let vec_total: usize = parse_match_counts(&input).iter().sum();
+    let vec_elapsed = start.elapsed();
+
+    let start = Instant::now();
+    let reader = BufReader::new(File::open(path).expect("File not found"));
+    let bitset_total: usize = read_cards(reader).map(|card| card.matches()).sum();
+    let bitset_elapsed = start.elapsed();
+
+    fs::remove_file(path).expect("Unable to remove file");
+
+    println!("{} cards", count);
+    println!("Vec contains: {} matches in {:?}", vec_total, vec_elapsed);
+    println!(
+        "Bitset:       {} matches in {:?}",
+        bitset_total, bitset_elapsed
+    );
+}
+
+fn explain(match_count: &[usize], propagation: &Propagation) {
+    for (card_index, &matches) in match_count.iter().enumerate() {
+        let sources = propagation.received[card_index]
+            .iter()
+            .map(|(source, copies)| format!("{} from card {}", copies, source + 1))
+            .collect::<Vec<String>>();
+
+        println!(
+            "Card {}: {} matches, {} instances ({})",
+            card_index + 1,
+            matches,
+            propagation.card_counts[card_index],
+            if sources.is_empty() {
+                "original only".to_string()
+            } else {
+                sources.join(", ")
+            }
+        );
+    }
+}
+
+fn to_dot(match_count: &[usize], propagation: &Propagation) -> String {
+    let mut lines = vec!["digraph scratchcards {".to_string()];
+
+    for (card_index, &matches) in match_count.iter().enumerate() {
+        lines.push(format!(
+            "    card{} [label=\"Card {}\\n{} matches\\n{} instances\"];",
+            card_index + 1,
+            card_index + 1,
+            matches,
+            propagation.card_counts[card_index]
+        ));
+    }
+
+    for (target_index, sources) in propagation.received.iter().enumerate() {
+        for (source, copies) in sources {
+            lines.push(format!(
+                "    card{} -> card{} [label=\"{}\"];",
+                source + 1,
+                target_index + 1,
+                copies
+            ));
+        }
+    }
+
+    lines.push("}".to_string());
+    lines.join("\n")
+}
+
+pub fn main() {
+    let args = env::args().skip(1).collect::<Vec<String>>();
+
+    if args.first().map(|arg| arg.as_str()) == Some("bench") {
+        let count = args
+            .get(1)
+            .map(|count| count.parse::<usize>().expect("Invalid card count"))
+            .unwrap_or(1_000_000);
+
+        bench(count);
+        return;
+    }
+
+    let reader = BufReader::new(File::open("src/04/input.txt").expect("File not found"));
+
+    let match_count = read_cards(reader)
+        .map(|card| card.matches())
+        .collect::<Vec<usize>>();
+    let propagation = propagate(&match_count).unwrap_or_else(|error| panic!("{}", error));
+
+    match args.first().map(|arg| arg.as_str()) {
+        Some("explain") => explain(&match_count, &propagation),
+        Some("dot") => println!("{}", to_dot(&match_count, &propagation)),
+        Some(command) => panic!("Unknown command {}", command),
+        None => {
+            let a: usize = match_count
+                .iter()
+                .map(|&count| if count > 0 { 1 << (count - 1) } else { 0 })
+                .sum();
+
+            let b: usize = propagation.card_counts.iter().sum();
+
+            println!("Answer a: {}", a);
+            println!("Answer b: {}", b);
+        }
+    }
 }
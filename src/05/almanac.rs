@@ -0,0 +1,260 @@
+use std::collections::{HashMap, VecDeque};
+use std::mem;
+
+pub type Table = Vec<(i64, i64, i64)>;
+
+pub struct AlmanacMap {
+    pub source: String,
+    pub destination: String,
+    pub table: Table,
+}
+
+pub struct Almanac {
+    pub seeds: Vec<i64>,
+    pub maps: Vec<AlmanacMap>,
+}
+
+impl Almanac {
+    pub fn new(input: &str) -> Self {
+        let mut sections: Vec<Vec<&str>> = Vec::new();
+        let mut section = Vec::new();
+
+        for line in input.lines().map(|line| line.trim()) {
+            if !line.is_empty() {
+                section.push(line);
+            } else if !section.is_empty() {
+                sections.push(mem::take(&mut section));
+            }
+        }
+        if !section.is_empty() {
+            sections.push(section);
+        }
+
+        let mut sections = sections.into_iter();
+
+        let seeds = sections
+            .next()
+            .expect("Missing seeds")
+            .join(" ")
+            .strip_prefix("seeds:")
+            .expect("Almanac must start with seeds")
+            .split_whitespace()
+            .map(|number| number.parse::<i64>().expect("Invalid seed"))
+            .collect();
+
+        let maps = sections
+            .map(|section| {
+                let mut lines = section.into_iter();
+
+                let header = lines.next().unwrap();
+                let (source, destination) = header
+                    .strip_suffix(" map:")
+                    .and_then(|name| name.split_once("-to-"))
+                    .unwrap_or_else(|| panic!("Invalid map header {}", header));
+
+                let table = lines
+                    .map(|line| {
+                        let result: Vec<i64> = line
+                            .split_whitespace()
+                            .map(|number| number.parse::<i64>().unwrap())
+                            .collect();
+
+                        match result[..] {
+                            [destination, source, range_length] => {
+                                (destination, source, range_length)
+                            }
+                            _ => panic!("Invalid map line {}", line),
+                        }
+                    })
+                    .collect();
+
+                AlmanacMap {
+                    source: source.to_string(),
+                    destination: destination.to_string(),
+                    table,
+                }
+            })
+            .collect();
+
+        Self { seeds, maps }
+    }
+
+    pub fn path(&self, from: &str, to: &str) -> Option<Vec<Table>> {
+        let mut previous: HashMap<&str, (&str, usize, bool)> = HashMap::new();
+        let mut queue = VecDeque::from([from]);
+
+        while let Some(category) = queue.pop_front() {
+            if category == to {
+                let mut tables = Vec::new();
+                let mut current = to;
+
+                while current != from {
+                    let (parent, index, forward) = previous[current];
+                    let map = &self.maps[index];
+
+                    tables.push(if forward {
+                        map.table.clone()
+                    } else {
+                        invert(&map.table).unwrap_or_else(|| {
+                            panic!(
+                                "{}-to-{} map is not one-to-one, so it cannot be reversed",
+                                map.source, map.destination
+                            )
+                        })
+                    });
+                    current = parent;
+                }
+
+                tables.reverse();
+                return Some(tables);
+            }
+
+            for (index, map) in self.maps.iter().enumerate() {
+                let edges = [
+                    (map.source.as_str(), map.destination.as_str(), true),
+                    (map.destination.as_str(), map.source.as_str(), false),
+                ];
+
+                for (source, destination, forward) in edges {
+                    if source == category
+                        && destination != from
+                        && !previous.contains_key(destination)
+                    {
+                        previous.insert(destination, (category, index, forward));
+                        queue.push_back(destination);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+fn total(table: &Table) -> Table {
+    let mut entries = table.clone();
+    entries.sort_by_key(|&(_, source, _)| source);
+
+    let mut result = Vec::new();
+    let mut cursor = 0;
+
+    for (destination, source, range_length) in entries {
+        if source > cursor {
+            result.push((cursor, cursor, source - cursor));
+        }
+
+        result.push((destination, source, range_length));
+        cursor = source + range_length;
+    }
+
+    if cursor < i64::MAX {
+        result.push((cursor, cursor, i64::MAX - cursor));
+    }
+
+    result
+}
+
+fn simplify(mut table: Table) -> Table {
+    table.sort_by_key(|&(_, source, _)| source);
+
+    let mut result: Table = Vec::new();
+
+    for (destination, source, range_length) in table {
+        if destination == source {
+            continue;
+        }
+
+        match result.last_mut() {
+            Some((last_destination, last_source, last_length))
+                if *last_source + *last_length == source
+                    && *last_destination + *last_length == destination =>
+            {
+                *last_length += range_length;
+            }
+            _ => result.push((destination, source, range_length)),
+        }
+    }
+
+    result
+}
+
+pub fn compose(first: &Table, second: &Table) -> Table {
+    let second = total(second);
+    let mut result = Vec::new();
+
+    for (destination, source, range_length) in total(first) {
+        let end = destination + range_length;
+
+        for &(second_destination, second_source, second_length) in second.iter() {
+            let overlap_start = destination.max(second_source);
+            let overlap_end = end.min(second_source + second_length);
+
+            if overlap_start < overlap_end {
+                result.push((
+                    second_destination + overlap_start - second_source,
+                    source + overlap_start - destination,
+                    overlap_end - overlap_start,
+                ));
+            }
+        }
+    }
+
+    simplify(result)
+}
+
+pub fn compose_all(tables: &[Table]) -> Table {
+    tables
+        .iter()
+        .fold(Vec::new(), |composed, table| compose(&composed, table))
+}
+
+pub fn invert(table: &Table) -> Option<Table> {
+    let mut images = total(table)
+        .iter()
+        .map(|&(destination, source, range_length)| (source, destination, range_length))
+        .collect::<Table>();
+    images.sort_by_key(|&(_, destination, _)| destination);
+
+    let bijective = images.first().map(|&(_, destination, _)| destination) == Some(0)
+        && images
+            .windows(2)
+            .all(|pair| pair[0].1 + pair[0].2 == pair[1].1);
+
+    if !bijective {
+        return None;
+    }
+
+    Some(simplify(images))
+}
+
+pub fn preimage(table: &Table, (start, end): (i64, i64)) -> Vec<(i64, i64)> {
+    let mut result = total(table)
+        .iter()
+        .filter_map(|&(destination, source, range_length)| {
+            let overlap_start = start.max(destination);
+            let overlap_end = end.min(destination + range_length);
+
+            if overlap_start < overlap_end {
+                Some((
+                    source + overlap_start - destination,
+                    source + overlap_end - destination,
+                ))
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<(i64, i64)>>();
+
+    result.sort();
+    result
+}
+
+pub fn format_table(source: &str, destination: &str, table: &Table) -> String {
+    let mut lines = vec![format!("{}-to-{} map:", source, destination)];
+
+    for (destination, source, range_length) in table {
+        lines.push(format!("{} {} {}", destination, source, range_length));
+    }
+
+    lines.join("\n")
+}
@@ -1,86 +1,286 @@
+#[path = "./almanac.rs"]
+mod almanac;
+
+use almanac::{Almanac, Table};
 use rayon::prelude::*;
+use std::env;
 use std::fs;
+use std::slice;
+use std::time::Instant;
+
+fn map_value(value: i64, tables: &[Table]) -> i64 {
+    let mut current_destination = value;
+    for target_table in tables {
+        current_destination = match target_table.iter().find(|&(_, source, range_length)| {
+            source + range_length > current_destination && current_destination + 1 > *source
+        }) {
+            Some((destination, source, _)) => destination + current_destination - source,
+            None => current_destination,
+        };
+    }
 
-fn get_answer(seeds: Vec<i64>, groups: &Vec<Vec<(i64, i64, i64)>>) -> i64 {
+    current_destination
+}
+
+fn get_answer(seeds: Vec<i64>, tables: &[Table]) -> i64 {
     seeds
         .par_iter()
-        .map(|&value| {
-            let mut current_destination = value;
-            for target_table in 1..groups.len() {
-                current_destination =
-                    match groups[target_table]
-                        .iter()
-                        .find(|&(_, source, range_length)| {
-                            source + range_length > current_destination
-                                && current_destination + 1 > *source
-                        }) {
-                        Some((destination, source, _)) => {
-                            destination + current_destination - source
-                        }
-                        None => current_destination,
-                    };
+        .map(|&value| map_value(value, tables))
+        .min()
+        .unwrap()
+}
+
+fn map_ranges(ranges: Vec<(i64, i64)>, table: &[(i64, i64, i64)]) -> Vec<(i64, i64)> {
+    let mut pending = ranges;
+    let mut mapped = Vec::new();
+
+    for &(destination, source, range_length) in table {
+        let source_end = source + range_length;
+        let mut remaining = Vec::new();
+
+        for (start, end) in pending {
+            let overlap_start = start.max(source);
+            let overlap_end = end.min(source_end);
+
+            if overlap_start >= overlap_end {
+                remaining.push((start, end));
+                continue;
             }
 
-            current_destination
-        })
+            mapped.push((
+                destination + overlap_start - source,
+                destination + overlap_end - source,
+            ));
+
+            if start < overlap_start {
+                remaining.push((start, overlap_start));
+            }
+            if overlap_end < end {
+                remaining.push((overlap_end, end));
+            }
+        }
+
+        pending = remaining;
+    }
+
+    mapped.extend(pending);
+    mapped
+}
+
+fn get_range_answer(ranges: Vec<(i64, i64)>, tables: &[Table]) -> i64 {
+    tables
+        .iter()
+        .fold(ranges, |ranges, table| map_ranges(ranges, table))
+        .iter()
+        .map(|(start, _)| *start)
         .min()
         .unwrap()
 }
 
+fn scan_answer(ranges: &[(i64, i64)], inverse: &Table) -> i64 {
+    (0..)
+        .find(|&location| {
+            let seed = map_value(location, slice::from_ref(inverse));
+
+            ranges
+                .iter()
+                .any(|&(start, end)| seed >= start && seed < end)
+        })
+        .unwrap()
+}
+
+fn intersect(a: &[(i64, i64)], b: &[(i64, i64)]) -> Vec<(i64, i64)> {
+    a.iter()
+        .flat_map(|&(a_start, a_end)| {
+            b.iter().filter_map(move |&(b_start, b_end)| {
+                let start = a_start.max(b_start);
+                let end = a_end.min(b_end);
+
+                (start < end).then_some((start, end))
+            })
+        })
+        .collect()
+}
+
 pub fn main() {
     let input = fs::read_to_string("src/05/input.txt").expect("File not found");
 
-    let mut groups: Vec<Vec<(i64, i64, i64)>> = vec![
-        vec![],
-        vec![],
-        vec![],
-        vec![],
-        vec![],
-        vec![],
-        vec![],
-        vec![],
-    ];
-
-    let mut index = 0;
-    input.lines().skip(2).for_each(|item| {
-        if item == "" {
-            index += 1;
-        } else if char::is_numeric(item.chars().next().unwrap()) {
-            let result: Vec<i64> = item
-                .split(" ")
-                .map(|number| number.parse::<i64>().unwrap())
-                .take(3)
-                .collect();
+    let args = env::args().skip(1).collect::<Vec<String>>();
+
+    let almanac = Almanac::new(&input);
 
-            let [destination, source, range_length] = [result[0], result[1], result[2]];
+    if args.first().map(|arg| arg.as_str()) == Some("query") {
+        let from = args.get(1).expect("Missing source category");
+        let to = args.get(2).expect("Missing destination category");
+        let tables = almanac
+            .path(from, to)
+            .unwrap_or_else(|| panic!("No path from {} to {}", from, to));
 
-            groups[index + 1].push((destination, source, range_length))
+        for value in args[3..].iter() {
+            let value = value.parse::<i64>().expect("Invalid value");
+            println!("{} {} -> {} {}", from, value, to, map_value(value, &tables));
         }
-    });
 
-    let a_seeds: Vec<i64> = input
-        .lines()
-        .next()
-        .unwrap()
-        .split(": ")
-        .nth(1)
-        .unwrap()
-        .split(" ")
-        .filter_map(|number| number.parse::<i64>().ok())
-        .collect();
+        return;
+    }
+
+    if args.first().map(|arg| arg.as_str()) == Some("compose") {
+        let from = args.get(1).map_or("seed", |arg| arg.as_str());
+        let to = args.get(2).map_or("location", |arg| arg.as_str());
+        let tables = almanac
+            .path(from, to)
+            .unwrap_or_else(|| panic!("No path from {} to {}", from, to));
 
-    let b_seeds = a_seeds
+        let composed = almanac::compose_all(&tables);
+        println!("{}", almanac::format_table(from, to, &composed));
+
+        return;
+    }
+
+    let tables = almanac
+        .path("seed", "location")
+        .expect("No path from seed to location");
+
+    let a_seeds = almanac.seeds.clone();
+
+    let b_ranges: Vec<(i64, i64)> = a_seeds
         .chunks(2)
-        .flat_map(|item| {
+        .map(|item| {
             let [seed, count] = [item[0], item[1]];
 
-            seed..(seed + count)
+            (seed, seed + count)
         })
         .collect();
 
-    let a = get_answer(a_seeds, &groups);
-    let b = get_answer(b_seeds, &groups);
+    match args.first().map(|arg| arg.as_str()) {
+        Some("check") => {
+            let b_seeds = b_ranges
+                .iter()
+                .flat_map(|&(start, end)| start..end)
+                .collect();
+
+            let expected = get_answer(b_seeds, &tables);
+            let actual = get_range_answer(b_ranges, &tables);
+
+            println!("Reference: {}", expected);
+            println!("Intervals: {}", actual);
+            assert_eq!(
+                expected, actual,
+                "Interval mapping disagrees with reference"
+            );
+        }
+        Some("reach") => {
+            let limit = args
+                .get(1)
+                .expect("Missing location limit")
+                .parse::<i64>()
+                .expect("Invalid location limit");
+
+            let composed = almanac::compose_all(&tables);
+            let seeds = intersect(&almanac::preimage(&composed, (0, limit + 1)), &b_ranges);
+
+            for (start, end) in seeds {
+                println!("Seeds {}..{} reach location <= {}", start, end - 1, limit);
+            }
+        }
+        Some("compare") => {
+            let start = Instant::now();
+            let intervals = get_range_answer(b_ranges.clone(), &tables);
+            println!("Intervals: {} in {:?}", intervals, start.elapsed());
+
+            let start = Instant::now();
+            let inverse = almanac::invert(&almanac::compose_all(&tables))
+                .expect("Seed to location map is not invertible");
+            let scan = scan_answer(&b_ranges, &inverse);
+            println!("Location scan: {} in {:?}", scan, start.elapsed());
+        }
+        Some(command) => panic!("Unknown command {}", command),
+        None => {
+            let a = get_answer(a_seeds, &tables);
+            let b = get_range_answer(b_ranges, &tables);
+
+            println!("Answer a: {}", a);
+            println!("Answer b: {}", b);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
 
-    println!("Answer a: {}", a);
-    println!("Answer b: {}", b);
+    fn sample_tables() -> Vec<Table> {
+        Almanac::new(SAMPLE).path("seed", "location").unwrap()
+    }
+
+    fn expand(ranges: &[(i64, i64)]) -> Vec<i64> {
+        ranges.iter().flat_map(|&(start, end)| start..end).collect()
+    }
+
+    #[test]
+    fn intervals_match_reference_on_sample() {
+        let tables = sample_tables();
+        let ranges = vec![(79, 93), (55, 68)];
+
+        assert_eq!(get_answer(expand(&ranges), &tables), 46);
+        assert_eq!(get_range_answer(ranges, &tables), 46);
+    }
+
+    #[test]
+    fn parses_crlf_almanac() {
+        let almanac = Almanac::new(&SAMPLE.replace('\n', "\r\n"));
+        let tables = almanac.path("seed", "location").unwrap();
+
+        assert_eq!(almanac.seeds, vec![79, 14, 55, 13]);
+        assert_eq!(get_answer(almanac.seeds.clone(), &tables), 35);
+    }
+
+    #[test]
+    fn intervals_match_reference_across_entry_boundaries() {
+        let tables = sample_tables();
+
+        for ranges in [vec![(95, 101)], vec![(40, 60)], vec![(0, 120)]] {
+            assert_eq!(
+                get_answer(expand(&ranges), &tables),
+                get_range_answer(ranges.clone(), &tables),
+                "Mismatch for {:?}",
+                ranges
+            );
+        }
+    }
 }
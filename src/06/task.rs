@@ -1,67 +1,315 @@
 use itertools::Itertools;
+use std::env;
 use std::fs;
 use std::iter::zip;
 
 struct Game {
-    distance: i64,
-    time: i64,
+    distance: i128,
+    time: i128,
+    acceleration: i128,
+}
+
+fn isqrt(value: i128) -> i128 {
+    if value < 2 {
+        return value;
+    }
+
+    let mut x = value / 2 + 1;
+    let mut y = (x + value / x) / 2;
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+
+    x
 }
 
 impl Game {
-    fn get_possible_wins_count(&self) -> i64 {
-        (0..self.time + 1)
-            .into_iter()
-            .map(|press_time| {
-                let speed = press_time;
-                let distance = self.time - press_time;
+    fn new(time: i128, distance: i128) -> Self {
+        Self {
+            distance,
+            time,
+            acceleration: 1,
+        }
+    }
 
-                speed * distance
-            })
-            .filter(|travel_distance| travel_distance > &self.distance)
+    fn travel_distance(&self, press_time: i128) -> i128 {
+        self.acceleration
+            .checked_mul(press_time)
+            .and_then(|speed| speed.checked_mul(self.time - press_time))
+            .expect("Travel distance is too large for exact arithmetic")
+    }
+
+    #[cfg(test)]
+    fn get_possible_wins_count(&self) -> i128 {
+        (0..self.time + 1)
+            .filter(|&press_time| self.travel_distance(press_time) > self.distance)
             .count()
             .try_into()
             .unwrap()
     }
+
+    fn winning_interval(&self) -> Option<(i128, i128)> {
+        if self.acceleration <= 0 {
+            panic!("Acceleration must be positive");
+        }
+
+        let record = self.distance.div_euclid(self.acceleration);
+        let square = self
+            .time
+            .checked_mul(self.time)
+            .expect("Race is too long for exact arithmetic");
+
+        if record >= square / 4 {
+            return None;
+        }
+
+        let discriminant = record
+            .checked_mul(4)
+            .and_then(|record| square.checked_sub(record))
+            .expect("Record is too large for exact arithmetic");
+
+        let mut low = ((self.time - isqrt(discriminant)) / 2).max(0);
+        while low > 0 && self.travel_distance(low - 1) > self.distance {
+            low -= 1;
+        }
+        while low <= self.time / 2 && self.travel_distance(low) <= self.distance {
+            low += 1;
+        }
+
+        let high = self.time - low;
+
+        (low <= high).then_some((low, high))
+    }
+
+    fn get_possible_wins_count_closed(&self) -> i128 {
+        match self.winning_interval() {
+            Some((low, high)) => high - low + 1,
+            None => 0,
+        }
+    }
+
+    fn optimal_press_time(&self) -> i128 {
+        self.time / 2
+    }
+
+    fn chart(&self, rows: i128) -> String {
+        let step = ((self.time + 1) + rows - 1) / rows;
+        let best = self.travel_distance(self.optimal_press_time()).max(1);
+        let width = 50;
+
+        let mut lines = Vec::new();
+        let mut press_time = 0;
+
+        while press_time <= self.time {
+            let travel_distance = self.travel_distance(press_time);
+            let bar = (travel_distance
+                .checked_mul(width)
+                .expect("Travel distance is too large to chart")
+                / best) as usize;
+            let marker = if travel_distance > self.distance {
+                '*'
+            } else {
+                ' '
+            };
+
+            lines.push(format!(
+                "{:>8} {} {:<width$} {}",
+                press_time,
+                marker,
+                "#".repeat(bar),
+                travel_distance,
+                width = width as usize
+            ));
+
+            press_time += step.max(1);
+        }
+
+        let record = (self
+            .distance
+            .checked_mul(width)
+            .expect("Record is too large to chart")
+            / best)
+            .clamp(0, width) as usize;
+        lines.push(format!(
+            "{:>8}   {}| record {}",
+            "",
+            " ".repeat(record),
+            self.distance
+        ));
+
+        lines.join("\n")
+    }
 }
 
-pub fn main() {
-    let input = fs::read_to_string("src/06/input.txt").expect("File not found");
+fn explore(games: &[Game]) {
+    for (index, game) in games.iter().enumerate() {
+        let press_time = game.optimal_press_time();
+        let best = game.travel_distance(press_time);
+
+        println!(
+            "Race {}: time {} ms, record {} mm, acceleration {} mm/ms per ms",
+            index + 1,
+            game.time,
+            game.distance,
+            game.acceleration
+        );
+        println!("  Optimal press time: {} ms ({} mm)", press_time, best);
 
-    let a: i64 = input
+        match game.winning_interval() {
+            Some((low, high)) => println!(
+                "  Winning interval: {}..={} ms ({} ways)",
+                low,
+                high,
+                high - low + 1
+            ),
+            None => println!("  Winning interval: none"),
+        }
+
+        println!("  Margin over record: {} mm", best - game.distance);
+        println!("{}", game.chart(20));
+        println!();
+    }
+}
+
+fn parse_games(input: &str) -> Vec<Game> {
+    let rows = input
         .lines()
+        .filter(|line| !line.trim().is_empty())
         .map(|line| {
-            line.split(":")
+            line.split(':')
                 .nth(1)
                 .unwrap()
-                .split(" ")
+                .split(' ')
                 .filter(|char| !char.is_empty())
-                .filter_map(|number| number.parse::<i64>().ok())
-                .collect::<Vec<i64>>()
-        })
-        .collect::<Vec<Vec<i64>>>()
-        .windows(2)
-        .flat_map(|window| {
-            zip(window[0].clone(), window[1].clone())
-                .map(|(time, distance)| Game { time, distance })
+                .filter_map(|number| number.parse::<i128>().ok())
+                .collect::<Vec<i128>>()
         })
-        .map(|game| game.get_possible_wins_count())
+        .collect::<Vec<Vec<i128>>>();
+
+    let games =
+        zip(rows[0].clone(), rows[1].clone()).map(|(time, distance)| Game::new(time, distance));
+
+    match rows.get(2) {
+        Some(accelerations) => games
+            .zip(accelerations.iter())
+            .map(|(game, &acceleration)| Game {
+                acceleration,
+                ..game
+            })
+            .collect(),
+        None => games.collect(),
+    }
+}
+
+pub fn main() {
+    let args = env::args().skip(1).collect::<Vec<String>>();
+
+    let input = fs::read_to_string("src/06/input.txt").expect("File not found");
+
+    let games = parse_games(&input);
+
+    if args.first().map(|arg| arg.as_str()) == Some("explore") {
+        let games = match args.get(1) {
+            Some(acceleration) => {
+                let acceleration = acceleration.parse::<i128>().expect("Invalid acceleration");
+
+                games
+                    .into_iter()
+                    .map(|game| Game {
+                        acceleration,
+                        ..game
+                    })
+                    .collect()
+            }
+            None => games,
+        };
+
+        explore(&games);
+        return;
+    }
+
+    let a: i128 = games
+        .iter()
+        .map(|game| game.get_possible_wins_count_closed())
         .product();
 
     let (time, distance) = input
         .lines()
+        .take(2)
         .flat_map(|line| {
-            line.split(":")
+            line.split(':')
                 .nth(1)
                 .unwrap()
-                .replace(" ", "")
-                .parse::<i64>()
+                .replace(' ', "")
+                .parse::<i128>()
                 .ok()
         })
         .collect_tuple()
         .unwrap();
 
-    let b = Game { distance, time }.get_possible_wins_count();
+    let acceleration = games.first().map_or(1, |game| game.acceleration);
+    let b = Game {
+        distance,
+        time,
+        acceleration,
+    }
+    .get_possible_wins_count_closed();
 
     println!("Answer a: {}", a);
     println!("Answer b: {}", b);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    fn assert_counts_match(game: &Game) {
+        assert_eq!(
+            game.get_possible_wins_count(),
+            game.get_possible_wins_count_closed(),
+            "Mismatch for time {} distance {} acceleration {}",
+            game.time,
+            game.distance,
+            game.acceleration
+        );
+    }
+
+    #[test]
+    fn closed_form_matches_brute_force_on_small_races() {
+        for time in 0..200 {
+            for distance in -1..=(time * time / 4 + 1) {
+                assert_counts_match(&Game::new(time, distance));
+            }
+        }
+    }
+
+    #[test]
+    fn closed_form_matches_brute_force_on_random_races() {
+        let mut rng = Rng::new(6);
+        for _ in 0..1000 {
+            let time = rng.range(0, 99_999) as i128;
+            assert_counts_match(&Game {
+                distance: rng.range(0, (time * time / 4 + 1) as usize) as i128,
+                time,
+                acceleration: rng.range(1, 5) as i128,
+            });
+        }
+    }
+
+    #[test]
+    fn odd_time_tie_has_no_winning_press() {
+        let game = Game::new(1_000_000_000_001, 250_000_000_000_500_000_000_000);
+
+        assert_eq!(game.winning_interval(), None);
+        assert_eq!(Game::new(7, 12).winning_interval(), None);
+        assert_eq!(Game::new(7, 11).winning_interval(), Some((3, 4)));
+    }
+
+    #[test]
+    fn isqrt_handles_extremes() {
+        assert_eq!(isqrt(2), 1);
+        assert_eq!(isqrt(i128::MAX), 13_043_817_825_332_782_212);
+    }
+}
@@ -0,0 +1,369 @@
+use std::cmp::Ordering;
+use std::ops::RangeInclusive;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HandType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+    FiveOfAKind,
+}
+
+#[derive(Debug, Clone)]
+pub struct RuleSet {
+    pub order: Vec<char>,
+    pub wild: Vec<char>,
+    pub straights: bool,
+    pub flushes: bool,
+    pub hand_sizes: RangeInclusive<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Card {
+    pub value: char,
+    pub suit: Option<char>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Hand {
+    pub cards: Vec<Card>,
+    pub resolved: Vec<Card>,
+    pub hand_type: HandType,
+    pub bet: usize,
+    ranks: Vec<usize>,
+}
+
+impl Card {
+    pub fn label(&self) -> String {
+        match self.suit {
+            Some(suit) => format!("{}{}", self.value, suit),
+            None => self.value.to_string(),
+        }
+    }
+}
+
+impl RuleSet {
+    pub fn new(order: &str, wild: &str) -> Self {
+        Self {
+            order: order.chars().collect(),
+            wild: wild.chars().collect(),
+            straights: false,
+            flushes: false,
+            hand_sizes: 5..=5,
+        }
+    }
+
+    pub fn standard() -> Self {
+        Self::new("23456789TJQKA", "")
+    }
+
+    pub fn jokers() -> Self {
+        Self::new("J23456789TQKA", "J")
+    }
+
+    pub fn rank(&self, card: char) -> usize {
+        self.order
+            .iter()
+            .position(|&value| value == card)
+            .unwrap_or_else(|| panic!("Unknown card {}", card))
+    }
+
+    pub fn is_wild(&self, card: char) -> bool {
+        self.wild.contains(&card)
+    }
+
+    fn run_length(cards: &[Card]) -> usize {
+        cards.len().min(5)
+    }
+
+    fn kind_type(&self, cards: &[Card]) -> HandType {
+        let mut counts = vec![0; self.order.len()];
+        let mut wild_count = 0;
+
+        for card in cards {
+            if self.is_wild(card.value) {
+                wild_count += 1;
+            } else {
+                counts[self.rank(card.value)] += 1;
+            }
+        }
+
+        counts.retain(|&count| count > 0);
+        counts.sort_by(|a, b| b.cmp(a));
+
+        match counts.first_mut() {
+            Some(count) => *count += wild_count,
+            None => counts.push(wild_count),
+        }
+
+        match counts[..] {
+            [count, ..] if count >= 5 => HandType::FiveOfAKind,
+            [4, ..] => HandType::FourOfAKind,
+            [3, count, ..] if count >= 2 => HandType::FullHouse,
+            [3, ..] => HandType::ThreeOfAKind,
+            [2, 2, ..] => HandType::TwoPair,
+            [2, ..] => HandType::OnePair,
+            _ => HandType::HighCard,
+        }
+    }
+
+    fn kind_target(&self, cards: &[Card]) -> char {
+        cards
+            .iter()
+            .filter(|card| !self.is_wild(card.value))
+            .max_by_key(|card| {
+                let count = cards
+                    .iter()
+                    .filter(|other| other.value == card.value)
+                    .count();
+
+                (count, self.rank(card.value))
+            })
+            .map_or(*self.order.last().unwrap(), |card| card.value)
+    }
+
+    fn straight_window(&self, cards: &[Card], length: usize) -> Option<usize> {
+        let wild_count = cards.iter().filter(|card| self.is_wild(card.value)).count();
+
+        let mut present = vec![false; self.order.len()];
+        for card in cards.iter().filter(|card| !self.is_wild(card.value)) {
+            present[self.rank(card.value)] = true;
+        }
+
+        if present.len() < length {
+            return None;
+        }
+
+        present.windows(length).rposition(|window| {
+            window.iter().filter(|&&value| value).count() + wild_count >= length
+        })
+    }
+
+    fn substitute(&self, cards: &[Card], values: &[char], suit: Option<char>) -> Vec<Card> {
+        let mut index = 0;
+
+        cards
+            .iter()
+            .map(|card| {
+                if !self.is_wild(card.value) {
+                    return *card;
+                }
+
+                let value = values.get(index).or(values.last()).unwrap_or(&card.value);
+                index += 1;
+
+                Card {
+                    value: *value,
+                    suit: suit.or(card.suit),
+                }
+            })
+            .collect()
+    }
+
+    fn resolve_straight(
+        &self,
+        cards: &[Card],
+        run: &[Card],
+        start: usize,
+        suit: Option<char>,
+    ) -> Vec<Card> {
+        let end = start + Self::run_length(cards);
+        let missing = self.order[start..end]
+            .iter()
+            .filter(|&&value| {
+                !run.iter()
+                    .any(|card| card.value == value && !self.is_wild(card.value))
+            })
+            .copied()
+            .collect::<Vec<char>>();
+
+        self.substitute(cards, &missing, suit)
+    }
+
+    fn flush_suits<'a>(
+        &'a self,
+        cards: &'a [Card],
+        length: usize,
+    ) -> impl Iterator<Item = Vec<Card>> + 'a {
+        let suits = cards
+            .iter()
+            .filter_map(|card| card.suit)
+            .collect::<Vec<char>>();
+
+        let mut seen = Vec::new();
+        suits.into_iter().filter_map(move |suit| {
+            if seen.contains(&suit) {
+                return None;
+            }
+            seen.push(suit);
+
+            let suited = cards
+                .iter()
+                .filter(|card| self.is_wild(card.value) || card.suit == Some(suit))
+                .copied()
+                .collect::<Vec<Card>>();
+
+            (suited.len() >= length).then_some(suited)
+        })
+    }
+
+    pub fn evaluate(&self, cards: &[Card]) -> (HandType, Vec<Card>) {
+        let length = Self::run_length(cards);
+        let target = self.kind_target(cards);
+
+        let mut best = (
+            self.kind_type(cards),
+            self.substitute(cards, &[target], None),
+        );
+        let mut consider = |hand_type: HandType, resolved: Vec<Card>| {
+            if hand_type > best.0 {
+                best = (hand_type, resolved);
+            }
+        };
+
+        if self.straights {
+            if let Some(start) = self.straight_window(cards, length) {
+                consider(
+                    HandType::Straight,
+                    self.resolve_straight(cards, cards, start, None),
+                );
+            }
+        }
+
+        if self.flushes {
+            for suited in self.flush_suits(cards, length) {
+                let suit = suited
+                    .iter()
+                    .find(|card| !self.is_wild(card.value))
+                    .and_then(|card| card.suit);
+
+                consider(HandType::Flush, self.substitute(cards, &[target], suit));
+
+                if self.straights {
+                    if let Some(start) = self.straight_window(&suited, length) {
+                        let resolved = self.resolve_straight(cards, &suited, start, suit);
+                        consider(HandType::StraightFlush, resolved);
+                    }
+                }
+            }
+        }
+
+        best
+    }
+
+    pub fn parse_cards(&self, cards_str: &str) -> Vec<Card> {
+        let chars = cards_str.chars().collect::<Vec<char>>();
+
+        let cards = if self.flushes {
+            chars
+                .chunks(2)
+                .map(|chunk| match chunk {
+                    [value, suit] => Card {
+                        value: *value,
+                        suit: Some(*suit),
+                    },
+                    _ => panic!("Card {} is missing a suit", chunk[0]),
+                })
+                .collect::<Vec<Card>>()
+        } else {
+            chars
+                .iter()
+                .map(|&value| Card { value, suit: None })
+                .collect::<Vec<Card>>()
+        };
+
+        if !self.hand_sizes.contains(&cards.len()) {
+            panic!(
+                "Hand {} has {} cards, expected {:?}",
+                cards_str,
+                cards.len(),
+                self.hand_sizes
+            );
+        }
+
+        cards
+    }
+}
+
+impl Hand {
+    pub fn new(cards_str: &str, bet_str: &str, rules: &RuleSet) -> Self {
+        let cards = rules.parse_cards(cards_str);
+        let ranks = cards.iter().map(|card| rules.rank(card.value)).collect();
+        let bet = bet_str.parse::<usize>().unwrap();
+        let (hand_type, resolved) = rules.evaluate(&cards);
+
+        Self {
+            cards,
+            resolved,
+            hand_type,
+            bet,
+            ranks,
+        }
+    }
+
+    pub fn substitutions(&self) -> Vec<(Card, Card)> {
+        self.cards
+            .iter()
+            .zip(self.resolved.iter())
+            .filter(|(card, resolved)| card != resolved)
+            .map(|(card, resolved)| (*card, *resolved))
+            .collect()
+    }
+
+    pub fn tie_breaker(&self, other: &Self) -> Option<usize> {
+        if self.hand_type != other.hand_type {
+            return None;
+        }
+
+        self.ranks
+            .iter()
+            .zip(other.ranks.iter())
+            .position(|(a, b)| a != b)
+    }
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.hand_type
+            .cmp(&other.hand_type)
+            .then_with(|| self.ranks.cmp(&other.ranks))
+    }
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Hand {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Hand {}
+
+pub fn parse_hands(input: &str, rules: &RuleSet) -> Vec<Hand> {
+    input
+        .lines()
+        .map(|line| line.split_once(' ').unwrap())
+        .map(|(cards, bet)| Hand::new(cards, bet, rules))
+        .collect()
+}
+
+pub fn total_winnings(hands: &mut [Hand]) -> usize {
+    hands.sort();
+
+    hands
+        .iter()
+        .enumerate()
+        .map(|(index, hand)| (index + 1) * hand.bet)
+        .sum()
+}
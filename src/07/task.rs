@@ -1,10 +1,169 @@
-#[path = "./task_a.rs"]
-mod task_a;
+#[path = "./cards.rs"]
+mod cards;
 
-#[path = "./task_b.rs"]
-mod task_b;
+use cards::RuleSet;
+use std::env;
+use std::fs;
+
+fn parse_rules(args: &[String]) -> RuleSet {
+    let mut positional = Vec::new();
+    let mut straights = false;
+    let mut flushes = false;
+    let mut hand_sizes = 5..=5;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--straights" => straights = true,
+            "--flushes" => flushes = true,
+            "--sizes" => {
+                let sizes = args.next().expect("Missing hand sizes");
+                let (min, max) = sizes.split_once('-').unwrap_or((sizes, sizes));
+
+                hand_sizes = min.parse::<usize>().expect("Invalid hand size")
+                    ..=max.parse::<usize>().expect("Invalid hand size");
+            }
+            _ => positional.push(arg.as_str()),
+        }
+    }
+
+    let mut rules = match positional[..] {
+        [] => RuleSet::standard(),
+        [order] => RuleSet::new(order, ""),
+        [order, wild, ..] => RuleSet::new(order, wild),
+    };
+
+    rules.straights = straights;
+    rules.flushes = flushes;
+    rules.hand_sizes = hand_sizes;
+    rules
+}
+
+fn report(hands: &mut [cards::Hand], csv: bool) {
+    hands.sort();
+
+    let header = [
+        "Rank",
+        "Hand",
+        "Type",
+        "Jokers",
+        "Bid",
+        "Winnings",
+        "Tie-break",
+    ];
+
+    let rows = hands
+        .iter()
+        .enumerate()
+        .map(|(index, hand)| {
+            let jokers = hand
+                .substitutions()
+                .iter()
+                .map(|(card, resolved)| format!("{}->{}", card.label(), resolved.label()))
+                .collect::<Vec<String>>();
+
+            let tie_break = match index.checked_sub(1).map(|previous| &hands[previous]) {
+                None => "-".to_string(),
+                Some(previous) => match hand.tie_breaker(previous) {
+                    Some(position) => format!(
+                        "card {} ({} > {})",
+                        position + 1,
+                        hand.cards[position].label(),
+                        previous.cards[position].label()
+                    ),
+                    None if hand.hand_type == previous.hand_type => "equal".to_string(),
+                    None => format!("type ({:?})", previous.hand_type),
+                },
+            };
+
+            vec![
+                (index + 1).to_string(),
+                hand.cards
+                    .iter()
+                    .map(|card| card.label())
+                    .collect::<String>(),
+                format!("{:?}", hand.hand_type),
+                if jokers.is_empty() {
+                    "-".to_string()
+                } else {
+                    jokers.join(" ")
+                },
+                hand.bet.to_string(),
+                ((index + 1) * hand.bet).to_string(),
+                tie_break,
+            ]
+        })
+        .collect::<Vec<Vec<String>>>();
+
+    if csv {
+        println!("{}", header.join(","));
+        for row in rows {
+            println!("{}", row.join(","));
+        }
+        return;
+    }
+
+    let widths = (0..header.len())
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].len())
+                .chain([header[column].len()])
+                .max()
+                .unwrap()
+        })
+        .collect::<Vec<usize>>();
+
+    let format_row = |row: Vec<&str>| {
+        row.iter()
+            .zip(widths.iter())
+            .map(|(value, width)| format!("{:<width$}", value, width = width))
+            .collect::<Vec<String>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    println!("{}", format_row(header.to_vec()));
+    for row in rows.iter() {
+        println!(
+            "{}",
+            format_row(row.iter().map(|value| value.as_str()).collect())
+        );
+    }
+}
 
 pub fn main() {
-    task_a::main();
-    task_b::main();
+    let input = fs::read_to_string("src/07/input.txt").expect("File not found");
+    let args = env::args().skip(1).collect::<Vec<String>>();
+
+    if args.first().map(|arg| arg.as_str()) == Some("report") {
+        let csv = args.iter().any(|arg| arg == "--csv");
+        let rule_args = args[1..]
+            .iter()
+            .filter(|arg| *arg != "--csv")
+            .cloned()
+            .collect::<Vec<String>>();
+
+        let rules = match rule_args.first().map(|arg| arg.as_str()) {
+            Some("jokers") => RuleSet::jokers(),
+            _ => parse_rules(&rule_args),
+        };
+
+        report(&mut cards::parse_hands(&input, &rules), csv);
+        return;
+    }
+
+    if !args.is_empty() {
+        let rules = parse_rules(&args);
+        let winnings = cards::total_winnings(&mut cards::parse_hands(&input, &rules));
+
+        println!("Winnings: {}", winnings);
+        return;
+    }
+
+    let a = cards::total_winnings(&mut cards::parse_hands(&input, &RuleSet::standard()));
+    let b = cards::total_winnings(&mut cards::parse_hands(&input, &RuleSet::jokers()));
+
+    println!("Answer a: {}", a);
+    println!("Answer b: {}", b);
 }
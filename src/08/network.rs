@@ -0,0 +1,167 @@
+use super::pattern::Pattern;
+use itertools::Itertools;
+use std::collections::HashMap;
+
+#[derive(Clone, Copy)]
+pub enum Move {
+    Left,
+    Right,
+}
+
+pub struct Network {
+    pub names: Vec<String>,
+    pub ids: HashMap<String, u32>,
+    pub edges: Vec<[u32; 2]>,
+}
+
+impl Network {
+    pub fn new<'a>(lines: impl Iterator<Item = &'a str>) -> Self {
+        let entries = lines
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                let (start, end_str) = line.split_once(" = ").unwrap();
+
+                let (left, right) = end_str
+                    .split(", ")
+                    .map(|value| value.trim_matches(['(', ')']))
+                    .next_tuple()
+                    .unwrap();
+
+                (start, left, right)
+            })
+            .collect::<Vec<(&str, &str, &str)>>();
+
+        let names = entries
+            .iter()
+            .map(|(name, _, _)| name.to_string())
+            .collect::<Vec<String>>();
+
+        let ids = names
+            .iter()
+            .enumerate()
+            .map(|(id, name)| (name.clone(), id as u32))
+            .collect::<HashMap<String, u32>>();
+
+        let id = |name: &str| {
+            *ids.get(name)
+                .unwrap_or_else(|| panic!("Unknown node {}", name))
+        };
+
+        let edges = entries
+            .iter()
+            .map(|(_, left, right)| [id(left), id(right)])
+            .collect();
+
+        Self { names, ids, edges }
+    }
+
+    pub fn id(&self, name: &str) -> Option<u32> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn step(&self, node: u32, m: Move) -> u32 {
+        self.edges[node as usize][m as usize]
+    }
+
+    pub fn flags(&self, pattern: &Pattern) -> Vec<bool> {
+        self.names
+            .iter()
+            .map(|name| pattern.matches(name))
+            .collect()
+    }
+
+    pub fn matching(&self, pattern: &Pattern) -> Vec<u32> {
+        (0..self.len() as u32)
+            .filter(|&id| pattern.matches(&self.names[id as usize]))
+            .collect()
+    }
+
+    pub fn reachable(&self, starts: &[u32]) -> Vec<bool> {
+        let mut seen = vec![false; self.len()];
+        let mut stack = starts.to_vec();
+
+        while let Some(node) = stack.pop() {
+            if seen[node as usize] {
+                continue;
+            }
+            seen[node as usize] = true;
+
+            stack.extend(self.edges[node as usize]);
+        }
+
+        seen
+    }
+
+    pub fn to_dot(&self, highlights: &[(Vec<(u32, usize)>, &str)], visible: &[bool]) -> String {
+        let mut edge_colours: HashMap<(u32, usize), Vec<&str>> = HashMap::new();
+        let mut node_colours: HashMap<u32, Vec<&str>> = HashMap::new();
+
+        for (edges, colour) in highlights {
+            for &(node, side) in edges {
+                let colours = edge_colours.entry((node, side)).or_default();
+                if !colours.contains(colour) {
+                    colours.push(colour);
+                }
+
+                let target = self.edges[node as usize][side];
+                for node in [node, target] {
+                    let colours = node_colours.entry(node).or_default();
+                    if !colours.contains(colour) {
+                        colours.push(colour);
+                    }
+                }
+            }
+        }
+
+        let mut lines = vec!["digraph network {".to_string()];
+
+        for (id, name) in self.names.iter().enumerate() {
+            if !visible[id] {
+                continue;
+            }
+
+            match node_colours.get(&(id as u32)) {
+                Some(colours) => lines.push(format!(
+                    "    \"{}\" [style=filled, fillcolor=\"{}\"];",
+                    name,
+                    colours.join(":")
+                )),
+                None => lines.push(format!("    \"{}\";", name)),
+            }
+        }
+
+        let hidden = visible.iter().filter(|&&visible| !visible).count();
+        if hidden > 0 {
+            lines.push(format!(
+                "    \"unreachable\" [shape=box, style=dashed, label=\"{} unreachable nodes\"];",
+                hidden
+            ));
+        }
+
+        for (id, targets) in self.edges.iter().enumerate() {
+            if !visible[id] {
+                continue;
+            }
+
+            for (side, label) in ["L", "R"].iter().enumerate() {
+                let target = targets[side];
+                let colour = edge_colours
+                    .get(&(id as u32, side))
+                    .map(|colours| format!(", color=\"{}\", penwidth=2", colours.join(":")))
+                    .unwrap_or_default();
+
+                lines.push(format!(
+                    "    \"{}\" -> \"{}\" [label=\"{}\"{}];",
+                    self.names[id], self.names[target as usize], label, colour
+                ));
+            }
+        }
+
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+}
@@ -0,0 +1,46 @@
+pub enum Pattern {
+    Exact(String),
+    Prefix(String),
+    Suffix(String),
+    Glob(Vec<char>),
+}
+
+fn glob_matches(pattern: &[char], name: &[char]) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, None) => true,
+        (Some('*'), _) => {
+            glob_matches(&pattern[1..], name)
+                || (!name.is_empty() && glob_matches(pattern, &name[1..]))
+        }
+        (Some('?'), Some(_)) => glob_matches(&pattern[1..], &name[1..]),
+        (Some(expected), Some(actual)) if expected == actual => {
+            glob_matches(&pattern[1..], &name[1..])
+        }
+        _ => false,
+    }
+}
+
+impl Pattern {
+    pub fn parse(value: &str) -> Self {
+        if let Some(prefix) = value.strip_prefix("prefix:") {
+            Pattern::Prefix(prefix.to_string())
+        } else if let Some(suffix) = value.strip_prefix("suffix:") {
+            Pattern::Suffix(suffix.to_string())
+        } else if let Some(glob) = value.strip_prefix("glob:") {
+            Pattern::Glob(glob.chars().collect())
+        } else if value.contains(['*', '?']) {
+            Pattern::Glob(value.chars().collect())
+        } else {
+            Pattern::Exact(value.to_string())
+        }
+    }
+
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            Pattern::Exact(exact) => name == exact,
+            Pattern::Prefix(prefix) => name.starts_with(prefix.as_str()),
+            Pattern::Suffix(suffix) => name.ends_with(suffix.as_str()),
+            Pattern::Glob(glob) => glob_matches(glob, &name.chars().collect::<Vec<char>>()),
+        }
+    }
+}
@@ -1,86 +1,541 @@
+#[path = "./network.rs"]
+mod network;
+#[path = "./pattern.rs"]
+mod pattern;
+
 use itertools::Itertools;
+use network::{Move, Network};
 use num_integer::Integer;
-use std::{collections::HashMap, fs};
+use pattern::Pattern;
+use std::{collections::HashMap, env, fs, ops::Range, time::Instant};
 
-enum Move {
-    Left,
-    Right,
+struct Cycle {
+    tail: i128,
+    length: i128,
+    tail_hits: Vec<i128>,
+    offsets: Vec<i128>,
 }
 
-fn get_path_length(
-    start: String,
-    is_end: fn(&String) -> bool,
-    moves: &Vec<Move>,
-    map: &HashMap<String, (String, String)>,
-) -> i128 {
-    let mut current_position = &start;
+enum Assumption {
+    Lcm,
+    SingleOffset,
+    MultipleOffsets,
+    FiniteEnds,
+    NeverEnds,
+}
+
+fn get_path_length(start: u32, is_end: &[bool], moves: &[Move], network: &Network) -> i128 {
+    let mut current_position = start;
 
     let mut index = 0;
     loop {
-        for m in moves.iter() {
-            let (left, right) = map.get(current_position).unwrap();
-
-            current_position = match m {
-                Move::Left => left,
-                Move::Right => right,
-            };
+        for &m in moves.iter() {
+            current_position = network.step(current_position, m);
 
             index += 1;
 
-            if is_end(current_position) {
+            if is_end[current_position as usize] {
                 return index;
             }
         }
     }
 }
 
-fn is_end_a(position: &String) -> bool {
-    position == "ZZZ"
+fn find_cycle(start: u32, is_end: &[bool], moves: &[Move], network: &Network) -> Cycle {
+    let mut seen: HashMap<(u32, usize), i128> = HashMap::new();
+    let mut hits = Vec::new();
+    let mut current_position = start;
+    let mut index: i128 = 0;
+
+    loop {
+        let instruction = (index % moves.len() as i128) as usize;
+
+        if let Some(&tail) = seen.get(&(current_position, instruction)) {
+            let (tail_hits, offsets) = hits.iter().partition(|&&hit| hit < tail);
+
+            return Cycle {
+                tail,
+                length: index - tail,
+                tail_hits,
+                offsets,
+            };
+        }
+
+        seen.insert((current_position, instruction), index);
+
+        if index > 0 && is_end[current_position as usize] {
+            hits.push(index);
+        }
+
+        current_position = network.step(current_position, moves[instruction]);
+        index += 1;
+    }
+}
+
+fn trace(start: u32, moves: &[Move], steps: Range<i128>, network: &Network) -> Vec<(u32, usize)> {
+    let mut current_position = start;
+    let mut edges = Vec::new();
+
+    for index in 0..steps.end {
+        let m = moves[(index % moves.len() as i128) as usize];
+
+        if index >= steps.start {
+            edges.push((current_position, m as usize));
+        }
+
+        current_position = network.step(current_position, m);
+    }
+
+    edges
+}
+
+impl Cycle {
+    fn is_hit(&self, time: i128) -> bool {
+        if time < self.tail {
+            return self.tail_hits.contains(&time);
+        }
+
+        self.offsets
+            .iter()
+            .any(|offset| (time - offset).rem_euclid(self.length) == 0)
+    }
 }
 
-fn is_end_b(position: &String) -> bool {
-    position.ends_with("Z")
+fn classify(cycles: &[Cycle]) -> Assumption {
+    if cycles
+        .iter()
+        .any(|cycle| cycle.offsets.is_empty() && cycle.tail_hits.is_empty())
+    {
+        Assumption::NeverEnds
+    } else if cycles.iter().any(|cycle| cycle.offsets.is_empty()) {
+        Assumption::FiniteEnds
+    } else if cycles.iter().all(|cycle| {
+        cycle.tail_hits.is_empty() && cycle.offsets.len() == 1 && cycle.offsets[0] == cycle.length
+    }) {
+        Assumption::Lcm
+    } else if cycles.iter().all(|cycle| cycle.offsets.len() == 1) {
+        Assumption::SingleOffset
+    } else {
+        Assumption::MultipleOffsets
+    }
 }
 
-pub fn main() {
-    let input = fs::read_to_string("src/08/input.txt").expect("File not found");
+fn combine(a: (i128, i128), b: (i128, i128)) -> Option<(i128, i128)> {
+    let (a_remainder, a_modulus) = a;
+    let (b_remainder, b_modulus) = b;
 
-    let moves: Vec<Move> = input
-        .lines()
-        .next()
-        .unwrap()
-        .chars()
+    let gcd = a_modulus.extended_gcd(&b_modulus);
+    let difference = b_remainder - a_remainder;
+
+    if difference % gcd.gcd != 0 {
+        return None;
+    }
+
+    let modulus = a_modulus / gcd.gcd * b_modulus;
+    let factor = (difference / gcd.gcd * gcd.x).rem_euclid(b_modulus / gcd.gcd);
+
+    Some((
+        (a_remainder + a_modulus * factor).rem_euclid(modulus),
+        modulus,
+    ))
+}
+
+fn synchronise(cycles: &[Cycle]) -> Option<i128> {
+    let assumption = classify(cycles);
+
+    match assumption {
+        Assumption::NeverEnds => return None,
+        Assumption::Lcm => {
+            return cycles
+                .iter()
+                .map(|cycle| cycle.length)
+                .reduce(|acc, value| Integer::lcm(&acc, &value));
+        }
+        _ => {}
+    }
+
+    let max_tail = cycles.iter().map(|cycle| cycle.tail).max().unwrap();
+
+    let early = (1..max_tail).find(|&time| cycles.iter().all(|cycle| cycle.is_hit(time)));
+    if early.is_some() {
+        return early;
+    }
+
+    if let Assumption::FiniteEnds = assumption {
+        return None;
+    }
+
+    cycles
+        .iter()
+        .map(|cycle| cycle.offsets.iter())
+        .multi_cartesian_product()
+        .filter_map(|offsets| {
+            offsets
+                .iter()
+                .zip(cycles.iter())
+                .map(|(&offset, cycle)| (offset.rem_euclid(cycle.length), cycle.length))
+                .try_fold((0, 1), combine)
+        })
+        .map(|(remainder, modulus)| {
+            let below = (max_tail - remainder).max(0);
+            remainder + (below + modulus - 1) / modulus * modulus
+        })
+        .min()
+}
+
+fn walk(starts: &[u32], cycles: &[Cycle], network: &Network) {
+    for (&start, cycle) in starts.iter().zip(cycles.iter()) {
+        match cycle.tail_hits.iter().chain(cycle.offsets.iter()).next() {
+            Some(steps) => println!("{}: {} steps", network.names[start as usize], steps),
+            None => println!("{}: never terminates", network.names[start as usize]),
+        }
+    }
+
+    let never = starts
+        .iter()
+        .zip(cycles.iter())
+        .filter(|(_, cycle)| cycle.tail_hits.is_empty() && cycle.offsets.is_empty())
+        .map(|(&start, _)| network.names[start as usize].as_str())
+        .collect::<Vec<&str>>();
+
+    if !never.is_empty() {
+        println!("Never terminate: {}", never.join(", "));
+    }
+
+    match synchronise(cycles) {
+        Some(steps) => println!("All end together after {} steps", steps),
+        None => println!("Starts never end on the same step"),
+    }
+}
+
+fn parse_moves(line: &str) -> Vec<Move> {
+    line.chars()
         .map(|char| match char {
             'L' => Move::Left,
             'R' => Move::Right,
             _ => panic!("Invalid move"),
         })
-        .collect();
+        .collect()
+}
+
+fn get_path_length_by_name(
+    start: &str,
+    end: &str,
+    moves: &[Move],
+    map: &HashMap<String, (String, String)>,
+) -> i128 {
+    let mut current_position = start.to_string();
+
+    let mut index = 0;
+    loop {
+        for m in moves.iter() {
+            let (left, right) = map.get(&current_position).unwrap();
+
+            current_position = match m {
+                Move::Left => left.clone(),
+                Move::Right => right.clone(),
+            };
+
+            index += 1;
+
+            if current_position == end {
+                return index;
+            }
+        }
+    }
+}
 
-    let map: HashMap<String, (String, String)> =
-        HashMap::from_iter(input.lines().skip(2).map(|line| {
-            let (start, end_str) = line.split_once(" = ").unwrap();
+fn bench(size: usize) {
+    let moves = parse_moves("RRLRRLRLRRRLRRL");
 
+    let mut input = String::new();
+    for i in 0..size {
+        let left = i.saturating_sub(1);
+        let right = (i + 1).min(size - 1);
+
+        input.push_str(&format!("N{} = (N{}, N{})\n", i, left, right));
+    }
+
+    let start = Instant::now();
+    let map: HashMap<String, (String, String)> = input
+        .lines()
+        .map(|line| {
+            let (name, end_str) = line.split_once(" = ").unwrap();
             let (left, right) = end_str
                 .split(", ")
-                .map(|value| value.replace("(", "").replace(")", ""))
+                .map(|value| value.replace(['(', ')'], ""))
                 .next_tuple()
                 .unwrap();
 
-            (start.to_string(), (left, right))
-        }));
+            (name.to_string(), (left, right))
+        })
+        .collect();
+    let end = format!("N{}", size - 1);
+    let strings = get_path_length_by_name("N0", &end, &moves, &map);
+    let strings_elapsed = start.elapsed();
+
+    let start = Instant::now();
+    let network = Network::new(input.lines());
+    let mut is_end = vec![false; network.len()];
+    is_end[network.id(&end).unwrap() as usize] = true;
+    let interned = get_path_length(network.id("N0").unwrap(), &is_end, &moves, &network);
+    let interned_elapsed = start.elapsed();
 
-    let a: i128 = get_path_length("AAA".to_string(), is_end_a, &moves, &map);
+    println!("{} nodes", size);
+    println!("Strings:  {} steps in {:?}", strings, strings_elapsed);
+    println!("Interned: {} steps in {:?}", interned, interned_elapsed);
+}
 
-    let b: i128 = input
-        .lines()
-        .skip(2)
-        .map(|line| line.split(" = ").next().unwrap().to_string())
-        .filter(|line| line.ends_with("A"))
-        .map(|position| get_path_length(position, is_end_b, &moves, &map))
-        .reduce(|acc, value| Integer::lcm(&acc, &value))
-        .unwrap();
-
-    println!("Answer a: {:?}", a);
-    println!("Answer b: {:?}", b);
+pub fn main() {
+    let args = env::args().skip(1).collect::<Vec<String>>();
+
+    if args.first().map(|arg| arg.as_str()) == Some("bench") {
+        let size = args
+            .get(1)
+            .map(|size| size.parse::<usize>().expect("Invalid size"))
+            .unwrap_or(1_000_000);
+
+        bench(size);
+        return;
+    }
+
+    let input = fs::read_to_string("src/08/input.txt").expect("File not found");
+
+    let moves = parse_moves(input.lines().next().unwrap());
+    let network = Network::new(input.lines().skip(2));
+
+    let (start_pattern, end_pattern) = match args.first().map(|arg| arg.as_str()) {
+        Some("walk") => (
+            Pattern::parse(args.get(1).expect("Missing start pattern")),
+            Pattern::parse(args.get(2).expect("Missing end pattern")),
+        ),
+        _ => (Pattern::parse("*A"), Pattern::parse("*Z")),
+    };
+
+    let starts = network.matching(&start_pattern);
+    if starts.is_empty() {
+        panic!("No start nodes match");
+    }
+
+    let is_end = network.flags(&end_pattern);
+
+    let cycles = starts
+        .iter()
+        .map(|&start| find_cycle(start, &is_end, &moves, &network))
+        .collect::<Vec<Cycle>>();
+
+    match args.first().map(|arg| arg.as_str()) {
+        Some("walk") => walk(&starts, &cycles, &network),
+        Some("dot") => {
+            let flags = &args[1..];
+            let mut highlights = Vec::new();
+
+            if flags.iter().any(|flag| flag == "--path") {
+                let start = network.id("AAA").expect("Missing node AAA");
+                let length = get_path_length(
+                    start,
+                    &network.flags(&Pattern::parse("ZZZ")),
+                    &moves,
+                    &network,
+                );
+
+                highlights.push((trace(start, &moves, 0..length, &network), "red"));
+            }
+
+            if flags.iter().any(|flag| flag == "--cycles") {
+                let palette = ["blue", "green", "orange", "purple", "brown", "cyan"];
+
+                for (index, (&start, cycle)) in starts.iter().zip(cycles.iter()).enumerate() {
+                    let steps = cycle.tail..cycle.tail + cycle.length;
+
+                    highlights.push((
+                        trace(start, &moves, steps, &network),
+                        palette[index % palette.len()],
+                    ));
+                }
+            }
+
+            let visible = if flags.iter().any(|flag| flag == "--collapse") {
+                let mut roots = starts.clone();
+                roots.extend(network.id("AAA"));
+
+                network.reachable(&roots)
+            } else {
+                vec![true; network.len()]
+            };
+
+            println!("{}", network.to_dot(&highlights, &visible));
+        }
+        Some("cycles") => {
+            for (&start, cycle) in starts.iter().zip(cycles.iter()) {
+                println!(
+                    "{}: tail {}, cycle length {}, Z before cycle {:?}, Z in cycle {:?}",
+                    network.names[start as usize],
+                    cycle.tail,
+                    cycle.length,
+                    cycle.tail_hits,
+                    cycle.offsets
+                );
+            }
+
+            match classify(&cycles) {
+                Assumption::Lcm => {
+                    println!("Every ghost ends exactly once per cycle at a multiple of its length: LCM is valid")
+                }
+                Assumption::SingleOffset => {
+                    println!(
+                        "Every ghost ends once per cycle, but offsets differ: solving with CRT"
+                    )
+                }
+                Assumption::MultipleOffsets => {
+                    println!(
+                        "Some ghosts end several times per cycle: solving CRT for each combination"
+                    )
+                }
+                Assumption::FiniteEnds => {
+                    println!("Some ghosts only end before entering their cycle: checking the tails")
+                }
+                Assumption::NeverEnds => println!("Some ghosts never reach an end node"),
+            }
+        }
+        Some(command) => panic!("Unknown command {}", command),
+        None => {
+            let a: i128 = get_path_length(
+                network.id("AAA").expect("Missing node AAA"),
+                &network.flags(&Pattern::parse("ZZZ")),
+                &moves,
+                &network,
+            );
+
+            let b = synchronise(&cycles).expect("Ghosts never end on the same step");
+
+            println!("Answer a: {:?}", a);
+            println!("Answer b: {:?}", b);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    fn cycle(tail: i128, length: i128, tail_hits: &[i128], offsets: &[i128]) -> Cycle {
+        Cycle {
+            tail,
+            length,
+            tail_hits: tail_hits.to_vec(),
+            offsets: offsets.to_vec(),
+        }
+    }
+
+    fn ghosts(input: &str) -> (Vec<Move>, Network, Vec<u32>, Vec<bool>) {
+        let moves = parse_moves(input.lines().next().unwrap());
+        let network = Network::new(input.lines().skip(2));
+        let starts = network.matching(&Pattern::parse("*A"));
+        let is_end = network.flags(&Pattern::parse("*Z"));
+
+        (moves, network, starts, is_end)
+    }
+
+    fn brute_force(input: &str, limit: i128) -> Option<i128> {
+        let (moves, network, mut positions, is_end) = ghosts(input);
+
+        (1..=limit).find(|&index| {
+            let m = moves[((index - 1) % moves.len() as i128) as usize];
+            for position in positions.iter_mut() {
+                *position = network.step(*position, m);
+            }
+
+            positions.iter().all(|&position| is_end[position as usize])
+        })
+    }
+
+    fn solve(input: &str) -> Option<i128> {
+        let (moves, network, starts, is_end) = ghosts(input);
+
+        let cycles = starts
+            .iter()
+            .map(|&start| find_cycle(start, &is_end, &moves, &network))
+            .collect::<Vec<Cycle>>();
+
+        synchronise(&cycles)
+    }
+
+    #[test]
+    fn combine_handles_non_coprime_moduli() {
+        assert_eq!(combine((2, 4), (4, 6)), Some((10, 12)));
+        assert_eq!(combine((0, 6), (3, 9)), Some((12, 18)));
+        assert_eq!(combine((1, 4), (2, 6)), None);
+    }
+
+    #[test]
+    fn classify_recognises_every_assumption() {
+        assert!(matches!(
+            classify(&[cycle(1, 2, &[], &[2]), cycle(1, 3, &[], &[3])]),
+            Assumption::Lcm
+        ));
+        assert!(matches!(
+            classify(&[cycle(1, 2, &[], &[2]), cycle(1, 6, &[], &[3])]),
+            Assumption::SingleOffset
+        ));
+        assert!(matches!(
+            classify(&[cycle(1, 2, &[], &[2]), cycle(1, 6, &[], &[3, 6])]),
+            Assumption::MultipleOffsets
+        ));
+        assert!(matches!(
+            classify(&[cycle(1, 2, &[], &[2]), cycle(3, 1, &[1], &[])]),
+            Assumption::FiniteEnds
+        ));
+        assert!(matches!(
+            classify(&[cycle(1, 2, &[], &[2]), cycle(0, 1, &[], &[])]),
+            Assumption::NeverEnds
+        ));
+    }
+
+    #[test]
+    fn synchronise_matches_brute_force_on_hand_built_networks() {
+        let networks = [
+            "LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\n22A = (22B, XXX)\n22B = (22C, 22C)\n22C = (22Z, 22Z)\n22Z = (22B, 22B)\nXXX = (XXX, XXX)",
+            "L\n\nAAA = (ABZ, ABZ)\nABZ = (ACC, ACC)\nACC = (ADD, ADD)\nADD = (ABZ, ABZ)\nBBA = (BBB, BBB)\nBBB = (BBZ, BBZ)\nBBZ = (BBB, BBB)",
+            "L\n\nAAA = (AAZ, AAZ)\nAAZ = (XXX, XXX)\nBBA = (BBZ, BBZ)\nBBZ = (BBB, BBB)\nBBB = (BBB, BBB)\nXXX = (XXX, XXX)",
+            "L\n\nAAA = (AAZ, AAZ)\nAAZ = (XXX, XXX)\nBBA = (BBB, BBB)\nBBB = (BBZ, BBZ)\nBBZ = (XXX, XXX)\nXXX = (XXX, XXX)",
+            "RL\n\nAAA = (XXX, XXX)\nBBA = (BBZ, BBZ)\nBBZ = (BBA, BBA)\nXXX = (XXX, XXX)",
+        ];
+
+        for input in networks {
+            assert_eq!(
+                solve(input),
+                brute_force(input, 1000),
+                "Mismatch on\n{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn synchronise_matches_brute_force_on_random_networks() {
+        let mut rng = Rng::new(8);
+        let names = ["AAA", "BBA", "CCZ", "DDZ", "EEE", "FFF", "GGZ", "HHH"];
+
+        for _ in 0..300 {
+            let moves = (0..rng.range(1, 4))
+                .map(|_| if rng.range(0, 1) == 0 { 'L' } else { 'R' })
+                .collect::<String>();
+
+            let mut input = format!("{}\n\n", moves);
+            for name in names {
+                let left = names[rng.range(0, names.len() - 1)];
+                let right = names[rng.range(0, names.len() - 1)];
+                input.push_str(&format!("{} = ({}, {})\n", name, left, right));
+            }
+
+            assert_eq!(
+                solve(&input),
+                brute_force(&input, 5000),
+                "Mismatch on\n{}",
+                input
+            );
+        }
+    }
 }
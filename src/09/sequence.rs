@@ -0,0 +1,141 @@
+use num_integer::Integer;
+
+pub struct Sequence {
+    pub values: Vec<i128>,
+    pub differences: Vec<i128>,
+    pub settled: bool,
+}
+
+impl Sequence {
+    pub fn new(values: Vec<i128>) -> Self {
+        let mut line = values.clone();
+        let mut differences = Vec::new();
+
+        while !line.iter().all(|&value| value == 0) {
+            differences.push(line[0]);
+
+            line = line
+                .windows(2)
+                .map(|pair| pair[1].checked_sub(pair[0]))
+                .collect::<Option<Vec<i128>>>()
+                .expect("Differences overflow i128");
+        }
+
+        Self {
+            values,
+            differences,
+            settled: !line.is_empty(),
+        }
+    }
+
+    pub fn degree(&self) -> Option<usize> {
+        self.differences.len().checked_sub(1)
+    }
+
+    pub fn polynomial(&self) -> Option<Vec<(i128, i128)>> {
+        let degree = self.degree().unwrap_or(0);
+        let denominator =
+            (1..=degree as i128).try_fold(1i128, |acc, value| acc.checked_mul(value))?;
+
+        let mut numerators = vec![0i128; degree + 1];
+        let mut falling = vec![1i128];
+        let mut factorial: i128 = 1;
+
+        for (level, &difference) in self.differences.iter().enumerate() {
+            if level > 0 {
+                let shift = level as i128 - 1;
+                let mut next = vec![0i128; falling.len() + 1];
+
+                for (power, &coefficient) in falling.iter().enumerate() {
+                    next[power + 1] = next[power + 1].checked_add(coefficient)?;
+                    next[power] = next[power].checked_sub(coefficient.checked_mul(shift)?)?;
+                }
+
+                falling = next;
+                factorial = factorial.checked_mul(level as i128)?;
+            }
+
+            let scale = difference.checked_mul(denominator / factorial)?;
+            for (power, &coefficient) in falling.iter().enumerate() {
+                numerators[power] =
+                    numerators[power].checked_add(coefficient.checked_mul(scale)?)?;
+            }
+        }
+
+        Some(
+            numerators
+                .into_iter()
+                .map(|numerator| {
+                    let gcd = numerator.gcd(&denominator);
+                    (numerator / gcd, denominator / gcd)
+                })
+                .collect(),
+        )
+    }
+
+    pub fn at(&self, index: i128) -> Option<i128> {
+        let mut binomial: i128 = 1;
+        let mut result: i128 = 0;
+
+        for (level, &difference) in self.differences.iter().enumerate() {
+            if level > 0 {
+                let level = level as i128;
+                binomial = binomial.checked_mul(index - level + 1)? / level;
+            }
+
+            result = result.checked_add(binomial.checked_mul(difference)?)?;
+        }
+
+        Some(result)
+    }
+
+    pub fn forward(&self, count: usize) -> Option<Vec<i128>> {
+        let length = self.values.len() as i128;
+
+        (length..length + count as i128)
+            .map(|index| self.at(index))
+            .collect()
+    }
+
+    pub fn backward(&self, count: usize) -> Option<Vec<i128>> {
+        (1..=count as i128).map(|offset| self.at(-offset)).collect()
+    }
+}
+
+pub fn format_polynomial(coefficients: &[(i128, i128)]) -> String {
+    let terms = coefficients
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, &(numerator, _))| numerator != 0)
+        .map(|(power, &(numerator, denominator))| {
+            let coefficient = match (numerator.abs(), denominator) {
+                (1, 1) if power > 0 => String::new(),
+                (numerator, 1) => numerator.to_string(),
+                (numerator, denominator) => format!("({}/{})", numerator, denominator),
+            };
+            let variable = match power {
+                0 => String::new(),
+                1 => "n".to_string(),
+                _ => format!("n^{}", power),
+            };
+
+            (numerator < 0, format!("{}{}", coefficient, variable))
+        })
+        .collect::<Vec<(bool, String)>>();
+
+    if terms.is_empty() {
+        return "0".to_string();
+    }
+
+    terms
+        .iter()
+        .enumerate()
+        .map(|(index, (negative, term))| match (index, negative) {
+            (0, true) => format!("-{}", term),
+            (0, false) => term.clone(),
+            (_, true) => format!(" - {}", term),
+            (_, false) => format!(" + {}", term),
+        })
+        .collect()
+}
@@ -1,76 +1,107 @@
-use std::fs;
-
-fn create_diff_lines(line: Vec<i32>) -> Vec<Vec<i32>> {
-    let mut result = vec![line];
-
-    loop {
-        let last = &result.last().unwrap();
-
-        if last.iter().all(|&value| value == 0) {
-            return result;
-        }
-
-        let diff_line = last
-            .windows(2)
-            .flat_map(<&[i32; 2]>::try_from)
-            .map(|[a, b]| b - a)
-            .collect::<Vec<i32>>();
-
-        result.push(diff_line)
-    }
-}
-
-fn extrapolate_forward(data_with_diffs: &Vec<Vec<i32>>) -> Vec<Vec<i32>> {
-    let mut result = data_with_diffs.clone();
-
-    for index in (1..result.len()).rev() {
-        let current_line_end = result[index].last().unwrap().clone();
-        let upper_line_end = result[index - 1].last().unwrap().clone();
-
-        result[index - 1].push(current_line_end + upper_line_end);
-    }
-
-    result
-}
-
-fn extrapolate_backwards(data_with_diffs: &Vec<Vec<i32>>) -> Vec<Vec<i32>> {
-    let mut result = data_with_diffs.clone();
-
-    for index in (1..result.len()).rev() {
-        let current_line_start = result[index].first().unwrap().clone();
-        let upper_line_start = result[index - 1].first().unwrap().clone();
-
-        result[index - 1].insert(0, upper_line_start - current_line_start);
-    }
-
-    result
+#[path = "./sequence.rs"]
+mod sequence;
+
+use itertools::Itertools;
+use sequence::Sequence;
+use std::{env, fs};
+
+fn parse_count(value: Option<&String>) -> usize {
+    value
+        .expect("Missing count")
+        .parse::<usize>()
+        .expect("Invalid count")
 }
 
 pub fn main() {
     let input = fs::read_to_string("src/09/input.txt").expect("File not found");
+    let args = env::args().skip(1).collect::<Vec<String>>();
 
-    let parsed_data: Vec<_> = input
+    let sequences: Vec<Sequence> = input
         .lines()
+        .filter(|line| !line.trim().is_empty())
         .map(|line| {
-            line.split(" ")
-                .filter_map(|value| value.parse::<i32>().ok())
+            line.split(' ')
+                .filter_map(|value| value.parse::<i128>().ok())
                 .collect()
         })
-        .map(create_diff_lines)
+        .map(Sequence::new)
         .collect();
 
-    let a: i32 = parsed_data
-        .iter()
-        .map(extrapolate_forward)
-        .map(|line| line[0].last().unwrap().clone())
-        .sum();
-
-    let b: i32 = parsed_data
-        .iter()
-        .map(extrapolate_backwards)
-        .map(|line| line[0].first().unwrap().clone())
-        .sum();
+    match args.first().map(|arg| arg.as_str()) {
+        Some("forward") => {
+            let count = parse_count(args.get(1));
 
-    println!("Answer a: {}", a);
-    println!("Answer b: {}", b);
+            for sequence in sequences.iter() {
+                let values = sequence
+                    .forward(count)
+                    .expect("Extrapolation overflows i128");
+                println!("{}", values.iter().join(" "));
+            }
+        }
+        Some("backward") => {
+            let count = parse_count(args.get(1));
+
+            for sequence in sequences.iter() {
+                let values = sequence
+                    .backward(count)
+                    .expect("Extrapolation overflows i128");
+                println!("{}", values.iter().join(" "));
+            }
+        }
+        Some("at") => {
+            let index = args
+                .get(1)
+                .expect("Missing index")
+                .parse::<i128>()
+                .expect("Invalid index");
+
+            for sequence in sequences.iter() {
+                match sequence.at(index) {
+                    Some(value) => println!("{}", value),
+                    None => println!("overflow"),
+                }
+            }
+        }
+        Some("classify") => {
+            for sequence in sequences.iter() {
+                let values = sequence.values.iter().join(" ");
+
+                if !sequence.settled {
+                    println!(
+                        "{}: never reaches all zeros within {} values",
+                        values,
+                        sequence.values.len()
+                    );
+                    continue;
+                }
+
+                let polynomial = sequence
+                    .polynomial()
+                    .map(|coefficients| sequence::format_polynomial(&coefficients))
+                    .unwrap_or_else(|| "overflows i128".to_string());
+
+                match sequence.degree() {
+                    Some(degree) => {
+                        println!("{}: degree {}, p(n) = {}", values, degree, polynomial)
+                    }
+                    None => println!("{}: all zeros, p(n) = 0", values),
+                }
+            }
+        }
+        Some(command) => panic!("Unknown command {}", command),
+        None => {
+            let a: i128 = sequences
+                .iter()
+                .map(|sequence| sequence.forward(1).expect("Extrapolation overflows i128")[0])
+                .sum();
+
+            let b: i128 = sequences
+                .iter()
+                .map(|sequence| sequence.backward(1).expect("Extrapolation overflows i128")[0])
+                .sum();
+
+            println!("Answer a: {}", a);
+            println!("Answer b: {}", b);
+        }
+    }
 }
@@ -1,5 +1,5 @@
 use rayon::prelude::*;
-use std::fs;
+use std::{env, fs, time::Instant};
 
 #[derive(Debug, PartialEq, Clone, Hash, Eq)]
 enum PipeType {
@@ -85,58 +85,58 @@ impl Board {
 
     fn is_allowed_move(source: &PointType, target: &PointType, direction: Direction) -> bool {
         match direction {
-            Direction::Up => match (source, target) {
+            Direction::Up => matches!(
+                (source, target),
                 (
                     PointType::Start
-                    | PointType::Pipe(PipeType::Vertical)
-                    | PointType::Pipe(PipeType::UpLeft)
-                    | PointType::Pipe(PipeType::UpRight),
+                        | PointType::Pipe(PipeType::Vertical)
+                        | PointType::Pipe(PipeType::UpLeft)
+                        | PointType::Pipe(PipeType::UpRight),
                     PointType::Start
-                    | PointType::Pipe(PipeType::Vertical)
-                    | PointType::Pipe(PipeType::DownLeft)
-                    | PointType::Pipe(PipeType::DownRight),
-                ) => true,
-                _ => false,
-            },
-            Direction::Right => match (source, target) {
+                        | PointType::Pipe(PipeType::Vertical)
+                        | PointType::Pipe(PipeType::DownLeft)
+                        | PointType::Pipe(PipeType::DownRight),
+                )
+            ),
+            Direction::Right => matches!(
+                (source, target),
                 (
                     PointType::Start
-                    | PointType::Pipe(PipeType::Horizontal)
-                    | PointType::Pipe(PipeType::UpRight)
-                    | PointType::Pipe(PipeType::DownRight),
+                        | PointType::Pipe(PipeType::Horizontal)
+                        | PointType::Pipe(PipeType::UpRight)
+                        | PointType::Pipe(PipeType::DownRight),
                     PointType::Start
-                    | PointType::Pipe(PipeType::Horizontal)
-                    | PointType::Pipe(PipeType::UpLeft)
-                    | PointType::Pipe(PipeType::DownLeft),
-                ) => true,
-                _ => false,
-            },
-            Direction::Down => match (source, target) {
+                        | PointType::Pipe(PipeType::Horizontal)
+                        | PointType::Pipe(PipeType::UpLeft)
+                        | PointType::Pipe(PipeType::DownLeft),
+                )
+            ),
+            Direction::Down => matches!(
+                (source, target),
                 (
                     PointType::Start
-                    | PointType::Pipe(PipeType::Vertical)
-                    | PointType::Pipe(PipeType::DownLeft)
-                    | PointType::Pipe(PipeType::DownRight),
+                        | PointType::Pipe(PipeType::Vertical)
+                        | PointType::Pipe(PipeType::DownLeft)
+                        | PointType::Pipe(PipeType::DownRight),
                     PointType::Start
-                    | PointType::Pipe(PipeType::Vertical)
-                    | PointType::Pipe(PipeType::UpLeft)
-                    | PointType::Pipe(PipeType::UpRight),
-                ) => true,
-                _ => false,
-            },
-            Direction::Left => match (source, target) {
+                        | PointType::Pipe(PipeType::Vertical)
+                        | PointType::Pipe(PipeType::UpLeft)
+                        | PointType::Pipe(PipeType::UpRight),
+                )
+            ),
+            Direction::Left => matches!(
+                (source, target),
                 (
                     PointType::Start
-                    | PointType::Pipe(PipeType::Horizontal)
-                    | PointType::Pipe(PipeType::UpLeft)
-                    | PointType::Pipe(PipeType::DownLeft),
+                        | PointType::Pipe(PipeType::Horizontal)
+                        | PointType::Pipe(PipeType::UpLeft)
+                        | PointType::Pipe(PipeType::DownLeft),
                     PointType::Start
-                    | PointType::Pipe(PipeType::Horizontal)
-                    | PointType::Pipe(PipeType::UpRight)
-                    | PointType::Pipe(PipeType::DownRight),
-                ) => true,
-                _ => false,
-            },
+                        | PointType::Pipe(PipeType::Horizontal)
+                        | PointType::Pipe(PipeType::UpRight)
+                        | PointType::Pipe(PipeType::DownRight),
+                )
+            ),
         }
     }
 
@@ -172,27 +172,89 @@ impl Board {
         valid_moves
     }
 
-    fn travel(&self, point: &Point, mut path: Vec<Point>, length: usize) -> Vec<Point> {
-        let valid_moves = self.get_valid_moves(&point);
+    fn start_pipe(&self, start: &Point) -> PipeType {
+        let (x, y) = start.position;
 
-        if path.len() > 1 {
-            if let Some(end) = valid_moves.iter().find(|point| point.t == PointType::Start) {
-                path.push(end.clone());
+        let neighbours = [
+            (Direction::Up, x, y - 1),
+            (Direction::Right, x + 1, y),
+            (Direction::Down, x, y + 1),
+            (Direction::Left, x - 1, y),
+        ];
 
-                return path;
-            }
+        let connected = neighbours
+            .into_iter()
+            .map(|(direction, x, y)| {
+                self.index(y as usize, x as usize).is_some_and(|neighbour| {
+                    Board::is_allowed_move(&start.t, &neighbour.t, direction)
+                })
+            })
+            .collect::<Vec<bool>>();
+
+        match connected[..] {
+            [true, false, true, false] => PipeType::Vertical,
+            [false, true, false, true] => PipeType::Horizontal,
+            [true, true, false, false] => PipeType::UpRight,
+            [true, false, false, true] => PipeType::UpLeft,
+            [false, false, true, true] => PipeType::DownLeft,
+            [false, true, true, false] => PipeType::DownRight,
+            _ => panic!(
+                "Start tile at {:?} has {} connections, expected exactly 2",
+                start.position,
+                connected.iter().filter(|&&value| value).count()
+            ),
         }
+    }
 
-        let move_point = valid_moves
-            .into_iter()
-            .find(|point| !path.contains(&point))
-            .unwrap();
+    fn substitute_start(&mut self) -> Point {
+        let index = self
+            .vec
+            .iter()
+            .position(|point| point.t == PointType::Start)
+            .expect("Missing start tile");
+
+        let pipe = self.start_pipe(&self.vec[index]);
+        self.vec[index].t = PointType::Pipe(pipe);
 
-        path.push(move_point.clone());
-        self.travel(&move_point, path, length)
+        self.vec[index].clone()
     }
 
-    fn is_inside_path(&self, point: &Point, path: &Vec<(i32, i32)>) -> bool {
+    fn offset(&self, (x, y): (i32, i32)) -> usize {
+        y as usize * self.col + x as usize
+    }
+
+    fn walk(&self, start: &Point) -> Vec<Point> {
+        let mut visited = vec![0u64; (self.row * self.col).div_ceil(64)];
+        let mut path = vec![start.clone()];
+
+        let offset = self.offset(start.position);
+        visited[offset / 64] |= 1 << (offset % 64);
+
+        loop {
+            let valid_moves = self.get_valid_moves(path.last().unwrap());
+
+            let next = valid_moves.iter().find(|point| {
+                let offset = self.offset(point.position);
+                visited[offset / 64] & (1 << (offset % 64)) == 0
+            });
+
+            match next {
+                Some(point) => {
+                    let offset = self.offset(point.position);
+                    visited[offset / 64] |= 1 << (offset % 64);
+
+                    path.push(point.clone());
+                }
+                None if path.len() > 2 && valid_moves.contains(start) => return path,
+                None => panic!(
+                    "Pipe loop is not closed at {:?}",
+                    path.last().unwrap().position
+                ),
+            }
+        }
+    }
+
+    fn is_inside_path(&self, point: &Point, path: &[(i32, i32)]) -> bool {
         if path.contains(&point.position) {
             return false;
         }
@@ -207,12 +269,12 @@ impl Board {
                 continue;
             }
 
-            let does_collide = match current_point.t {
+            let does_collide = matches!(
+                current_point.t,
                 PointType::Pipe(PipeType::Vertical)
-                | PointType::Pipe(PipeType::UpLeft)
-                | PointType::Pipe(PipeType::UpRight) => true,
-                _ => false,
-            };
+                    | PointType::Pipe(PipeType::UpLeft)
+                    | PointType::Pipe(PipeType::UpRight)
+            );
 
             if does_collide {
                 collisions += 1;
@@ -221,11 +283,143 @@ impl Board {
 
         collisions % 2 == 1
     }
+
+    fn enclosed_by_area(path: &[Point]) -> usize {
+        let twice_area: i64 = path
+            .iter()
+            .zip(path.iter().cycle().skip(1))
+            .map(|(a, b)| {
+                let (x1, y1) = a.position;
+                let (x2, y2) = b.position;
+
+                x1 as i64 * y2 as i64 - x2 as i64 * y1 as i64
+            })
+            .sum();
+
+        (twice_area.unsigned_abs() as usize + 2 - path.len()) / 2
+    }
+
+    fn enclosed_by_reference(&self, path: &[Point]) -> usize {
+        let path_positions = path
+            .iter()
+            .map(|point| point.position)
+            .collect::<Vec<(i32, i32)>>();
+
+        self.vec
+            .par_iter()
+            .filter(|point| self.is_inside_path(point, &path_positions))
+            .count()
+    }
+
+    fn enclosed_by_scanline(&self, path: &[Point]) -> usize {
+        let mut on_path = vec![false; self.row * self.col];
+        for point in path {
+            on_path[self.offset(point.position)] = true;
+        }
+
+        let mut count = 0;
+        for row in 0..self.row {
+            let mut inside = false;
+
+            for col in 0..self.col {
+                let point = self.index(row, col).unwrap();
+
+                if !on_path[self.offset(point.position)] {
+                    count += inside as usize;
+                    continue;
+                }
+
+                if matches!(
+                    point.t,
+                    PointType::Pipe(PipeType::Vertical)
+                        | PointType::Pipe(PipeType::UpLeft)
+                        | PointType::Pipe(PipeType::UpRight)
+                ) {
+                    inside = !inside;
+                }
+            }
+        }
+
+        count
+    }
 }
 
-pub fn main() {
-    let input = fs::read_to_string("src/10/input.txt").expect("File not found");
+fn draw_loop(positions: &[(usize, usize)], start: usize, width: usize, height: usize) -> String {
+    let mut grid = vec![vec!['.'; width]; height];
+
+    for (index, &(x, y)) in positions.iter().enumerate() {
+        let previous = positions[(index + positions.len() - 1) % positions.len()];
+        let next = positions[(index + 1) % positions.len()];
+
+        let up = previous.1 < y || next.1 < y;
+        let down = previous.1 > y || next.1 > y;
+        let left = previous.0 < x || next.0 < x;
+        let right = previous.0 > x || next.0 > x;
+
+        grid[y][x] = match (up, down, left, right) {
+            _ if index == start => 'S',
+            (true, true, _, _) => '|',
+            (_, _, true, true) => '-',
+            (true, _, _, true) => 'L',
+            (true, _, true, _) => 'J',
+            (_, true, true, _) => '7',
+            (_, true, _, true) => 'F',
+            _ => unreachable!(),
+        };
+    }
+
+    grid.iter()
+        .map(|row| row.iter().collect::<String>())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn generate_loop(side: usize) -> String {
+    let mut positions = (0..side).map(|x| (x, 0)).collect::<Vec<(usize, usize)>>();
+
+    for y in 1..side {
+        if y % 2 == 1 {
+            positions.extend((1..side).rev().map(|x| (x, y)));
+        } else {
+            positions.extend((1..side).map(|x| (x, y)));
+        }
+    }
+    positions.extend((1..side).rev().map(|y| (0, y)));
 
+    draw_loop(&positions, 0, side, side)
+}
+
+#[cfg(test)]
+fn generate_histogram(columns: &[usize], height: usize, start: usize) -> String {
+    let mut corners = vec![(0, height)];
+    for (x, &column) in columns.iter().enumerate() {
+        corners.push((x, height - column));
+        corners.push((x + 1, height - column));
+    }
+    corners.push((columns.len(), height));
+
+    let mut positions = Vec::new();
+    for (index, &(x, y)) in corners.iter().enumerate() {
+        let (next_x, next_y) = corners[(index + 1) % corners.len()];
+        let (mut x, mut y) = (2 * x, 2 * y);
+
+        while (x, y) != (2 * next_x, 2 * next_y) {
+            positions.push((x, y));
+
+            x = (x as isize + (2 * next_x).cmp(&x) as isize) as usize;
+            y = (y as isize + (2 * next_y).cmp(&y) as isize) as usize;
+        }
+    }
+
+    draw_loop(
+        &positions,
+        start % positions.len(),
+        2 * columns.len() + 1,
+        2 * height + 1,
+    )
+}
+
+fn parse_board(input: &str) -> Board {
     let rows = input.lines().count();
     let cols = input.lines().next().unwrap().len();
 
@@ -234,28 +428,173 @@ pub fn main() {
         .flat_map(|line| line.chars().collect::<Vec<char>>())
         .collect::<Vec<char>>();
 
-    let board = Board::new(cells, rows, cols);
+    Board::new(cells, rows, cols)
+}
+
+fn find_loop(board: &mut Board) -> Vec<Point> {
+    let start = board.substitute_start();
+
+    board.walk(&start)
+}
 
-    let start = board
-        .vec
-        .iter()
-        .find(|point| point.t == PointType::Start)
-        .unwrap();
+fn bench(side: usize) {
+    if side < 2 || side % 2 == 1 {
+        panic!("Loop side must be even and at least 2");
+    }
 
-    let path = board.travel(start, vec![], 0);
+    let input = generate_loop(side);
+    let mut board = parse_board(&input);
 
-    let path_positions = path
-        .iter()
-        .map(|point| point.position)
-        .collect::<Vec<(i32, i32)>>();
+    let start = Instant::now();
+    let path = find_loop(&mut board);
+    let elapsed = start.elapsed();
 
-    let a = path.len() / 2;
-    let b: usize = board
-        .vec
-        .par_iter()
-        .filter(|point| board.is_inside_path(point, &path_positions))
-        .count();
+    println!("{} tiles walked in {:?}", path.len(), elapsed);
+}
+
+pub fn main() {
+    let args = env::args().skip(1).collect::<Vec<String>>();
+
+    if args.first().map(|arg| arg.as_str()) == Some("bench") {
+        let side = args
+            .get(1)
+            .map(|side| side.parse::<usize>().expect("Invalid side"))
+            .unwrap_or(1000);
+
+        bench(side);
+        return;
+    }
 
-    println!("Answer a: {:?}", a);
+    let input = fs::read_to_string("src/10/input.txt").expect("File not found");
+
+    let mut board = parse_board(&input);
+    let path = find_loop(&mut board);
+
+    let b = match args.first().map(|arg| arg.as_str()) {
+        Some("method") => match args.get(1).map(|arg| arg.as_str()) {
+            Some("area") => Board::enclosed_by_area(&path),
+            Some("scanline") => board.enclosed_by_scanline(&path),
+            Some("reference") => board.enclosed_by_reference(&path),
+            Some(method) => panic!("Unknown method {}", method),
+            None => panic!("Missing method, expected area, scanline or reference"),
+        },
+        Some(command) => panic!("Unknown command {}", command),
+        None => Board::enclosed_by_area(&path),
+    };
+
+    println!("Answer a: {:?}", path.len() / 2);
     println!("Answer b: {:?}", b);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    const SAMPLE: &str = "...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........";
+
+    const VERTICAL_START: &str = "F--7
+|..|
+S..|
+L--J";
+
+    const UP_LEFT_START: &str = "....F--7
+....|..|
+F---S..|
+|......|
+L------J";
+
+    fn enclosed(input: &str) -> usize {
+        let mut board = parse_board(input);
+        let path = find_loop(&mut board);
+
+        let reference = board.enclosed_by_reference(&path);
+
+        assert_eq!(
+            Board::enclosed_by_area(&path),
+            reference,
+            "Shoelace disagrees on\n{}",
+            input
+        );
+        assert_eq!(
+            board.enclosed_by_scanline(&path),
+            reference,
+            "Scanline disagrees on\n{}",
+            input
+        );
+
+        reference
+    }
+
+    #[test]
+    fn methods_agree_on_sample() {
+        assert_eq!(enclosed(SAMPLE), 4);
+    }
+
+    #[test]
+    fn methods_agree_when_start_is_a_bend_or_vertical_pipe() {
+        assert_eq!(enclosed(VERTICAL_START), 4);
+        assert_eq!(enclosed(UP_LEFT_START), 10);
+    }
+
+    #[test]
+    fn start_pipe_is_inferred_from_neighbours() {
+        let mut board = parse_board(VERTICAL_START);
+        assert_eq!(
+            board.substitute_start().t,
+            PointType::Pipe(PipeType::Vertical)
+        );
+
+        let mut board = parse_board(UP_LEFT_START);
+        assert_eq!(
+            board.substitute_start().t,
+            PointType::Pipe(PipeType::UpLeft)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "has 3 connections, expected exactly 2")]
+    fn start_with_three_connections_is_rejected() {
+        parse_board(".|.\n-S-\n...").substitute_start();
+    }
+
+    #[test]
+    fn methods_agree_on_generated_histograms() {
+        let mut rng = Rng::new(10);
+
+        for _ in 0..200 {
+            let height = rng.range(1, 12);
+            let width = rng.range(1, 20);
+            let columns = (0..width)
+                .map(|_| rng.range(1, height))
+                .collect::<Vec<usize>>();
+
+            let start = rng.range(0, usize::MAX - 1);
+            enclosed(&generate_histogram(&columns, height, start));
+        }
+    }
+
+    #[test]
+    fn walks_generated_million_tile_loop() {
+        let side = 1000;
+        let mut board = parse_board(&generate_loop(side));
+        let path = find_loop(&mut board);
+
+        assert_eq!(path.len(), side * side, "Walk missed part of the loop");
+        for point in path.iter() {
+            assert_eq!(
+                board.index(point.position.1 as usize, point.position.0 as usize),
+                Some(point),
+                "Walk returned a tile that is not on the board"
+            );
+        }
+    }
+}
@@ -1,6 +1,9 @@
-#[path = "02/task.rs"]
+#[path = "10/task.rs"]
 mod task;
 
+#[allow(dead_code)]
+mod rng;
+
 fn main() {
     task::main();
 }